@@ -10,4 +10,92 @@ pub enum MarketplaceError {
     InsufficientFunds,
     #[msg("Arithmetic overflow")]
     ArithmeticOverflow,
+    #[msg("Royalty config does not belong to this ticket's event")]
+    RoyaltyConfigMismatch,
+    #[msg("Listing has expired")]
+    ListingExpired,
+    #[msg("Unauthorized")]
+    Unauthorized,
+    #[msg("Listing does not accept offers")]
+    OffersNotAllowed,
+    #[msg("Offer has expired")]
+    OfferExpired,
+    #[msg("Offer is not active")]
+    OfferNotActive,
+    #[msg("Listing is still active")]
+    ListingStillActive,
+    #[msg("Listing has not expired yet")]
+    ListingNotExpired,
+    #[msg("Offer was made on an earlier listing of this ticket")]
+    StaleOffer,
+    #[msg("Offer has not been countered")]
+    NoCounterOffer,
+    #[msg("Counter offer limit reached")]
+    TooManyCounterRounds,
+    #[msg("Invalid counter offer amount")]
+    InvalidCounterAmount,
+    #[msg("Offer has not expired yet")]
+    OfferNotExpired,
+    #[msg("Auction is not active")]
+    AuctionNotActive,
+    #[msg("Auction has ended")]
+    AuctionEnded,
+    #[msg("Auction has not ended yet")]
+    AuctionStillRunning,
+    #[msg("Wrong auction type for this operation")]
+    InvalidAuctionType,
+    #[msg("Bid is too low")]
+    BidTooLow,
+    #[msg("Invalid auction configuration")]
+    InvalidAuctionConfig,
+    #[msg("Reserve reveal does not match the commitment")]
+    InvalidReserveReveal,
+    #[msg("Reserve price has not been revealed yet")]
+    ReserveRevealPending,
+    #[msg("Auction already has bids")]
+    AuctionHasBids,
+    #[msg("Auction is not in its reveal phase")]
+    NotInRevealPhase,
+    #[msg("Sealed bid already revealed")]
+    BidAlreadyRevealed,
+    #[msg("Revealed bid does not match the commitment")]
+    InvalidBidReveal,
+    #[msg("Sealed bid deposits are locked until the reveal phase ends")]
+    RevealPhaseActive,
+    #[msg("Previous bidder account does not match the auction")]
+    PreviousBidderMismatch,
+    #[msg("Payment mint does not match the listing or auction")]
+    PaymentMintMismatch,
+    #[msg("Missing or invalid payment token account")]
+    InvalidPaymentAccount,
+    #[msg("Royalty recipients must be 1-10 entries totalling at most 100%")]
+    InvalidRoyaltyConfig,
+    #[msg("A royalty update is already pending")]
+    RoyaltyUpdatePending,
+    #[msg("No royalty update is pending")]
+    NoPendingRoyaltyUpdate,
+    #[msg("Royalty update is still time-locked")]
+    RoyaltyUpdateTimelocked,
+    #[msg("Royalty recipient accounts do not match the royalty config")]
+    RoyaltyRecipientMismatch,
+    #[msg("Event account does not match the ticket")]
+    EventMismatch,
+    #[msg("Event has been cancelled")]
+    EventCancelled,
+    #[msg("Event has not been cancelled")]
+    EventNotCancelled,
+    #[msg("Bundle must hold 1-8 distinct tickets priced in one currency")]
+    InvalidBundle,
+    #[msg("Bundle ticket accounts do not match the bundle")]
+    BundleAccountMismatch,
+    #[msg("Cart must hold 1-5 listings")]
+    InvalidCart,
+    #[msg("Cart accounts do not match the listings")]
+    CartAccountMismatch,
+    #[msg("No listing in the cart could be bought")]
+    NothingPurchased,
+    #[msg("Sealed bid was committed to an earlier auction of this ticket")]
+    StaleSealedBid,
+    #[msg("Price cap multiplier must be at least 100% of face value")]
+    InvalidPriceCap,
 }
@@ -0,0 +1,183 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use ticket_nft_core::state::Event;
+
+use crate::state::*;
+use crate::errors::*;
+use crate::payment::*;
+
+use super::buy_ticket::release_listing_escrow;
+use super::accept_offer::pay_out_offer;
+
+/// Remaining accounts: one per royalty recipient, in RoyaltyConfig order (see `pay_royalties`)
+#[derive(Accounts)]
+pub struct AcceptCounterOffer<'info> {
+    /// Countered offer; closed back to the buyer once settled
+    #[account(
+        mut,
+        seeds = [b"offer", listing.key().as_ref(), buyer.key().as_ref(), &offer.listing_nonce.to_le_bytes()],
+        bump = offer.bump,
+        close = buyer,
+        constraint = offer.status == OfferStatus::CounterOffered @ MarketplaceError::NoCounterOffer,
+        constraint = offer.listing_nonce == listing.nonce @ MarketplaceError::StaleOffer
+    )]
+    pub offer: Account<'info, Offer>,
+
+    #[account(
+        mut,
+        seeds = [b"listing", listing.ticket_mint.as_ref()],
+        bump = listing.bump,
+        close = seller,
+        constraint = listing.status == ListingStatus::Active @ MarketplaceError::ListingNotActive
+    )]
+    pub listing: Account<'info, Listing>,
+
+    /// Buyer accepting the seller's counter price
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    /// Seller's account to receive payment
+    #[account(
+        mut,
+        constraint = seller.key() == listing.seller
+    )]
+    pub seller: SystemAccount<'info>,
+
+    /// Buyer's token account to receive ticket
+    #[account(
+        mut,
+        constraint = buyer_token_account.mint == listing.ticket_mint,
+        constraint = buyer_token_account.owner == offer.buyer
+    )]
+    pub buyer_token_account: Account<'info, TokenAccount>,
+
+    /// Escrow token account holding the ticket
+    #[account(
+        mut,
+        seeds = [b"escrow", listing.key().as_ref()],
+        bump,
+        constraint = escrow_token_account.amount == 1
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+
+    /// Listed ticket's event; nothing settles once it's cancelled
+    #[account(
+        constraint = event.key() == listing.event @ MarketplaceError::EventMismatch,
+        constraint = !event.cancelled @ MarketplaceError::EventCancelled
+    )]
+    pub event: Box<Account<'info, Event>>,
+
+    /// Royalty configuration of the listed ticket's event; any other event's config is rejected
+    #[account(
+        seeds = [b"royalty_config", royalty_config.event.as_ref()],
+        bump = royalty_config.bump,
+        constraint = royalty_config.event == listing.event @ MarketplaceError::RoyaltyConfigMismatch
+    )]
+    pub royalty_config: Account<'info, RoyaltyConfig>,
+
+    /// Resale totals for the event, updated with this sale
+    #[account(
+        mut,
+        seeds = [b"royalty_stats", listing.event.as_ref()],
+        bump = royalty_stats.bump
+    )]
+    pub royalty_stats: Account<'info, RoyaltyStats>,
+
+    /// Buyer's token account funding the top-up of a token-denominated offer
+    #[account(mut)]
+    pub buyer_payment_account: Option<Account<'info, TokenAccount>>,
+
+    /// SPL mint of a token-denominated offer
+    pub payment_mint: Option<Account<'info, Mint>>,
+
+    /// Token escrow of a token-denominated offer, paid out and closed to the buyer
+    #[account(
+        mut,
+        seeds = [b"payment_escrow", offer.key().as_ref()],
+        bump
+    )]
+    pub payment_escrow: Option<Account<'info, TokenAccount>>,
+
+    /// Seller's associated token account receiving a token-denominated sale
+    #[account(mut)]
+    pub seller_payment_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, AcceptCounterOffer<'info>>) -> Result<()> {
+    let clock = Clock::get()?;
+
+    require!(ctx.accounts.offer.expires_at > clock.unix_timestamp, MarketplaceError::OfferExpired);
+    require!(!ctx.accounts.listing.is_expired(clock.unix_timestamp), MarketplaceError::ListingExpired);
+
+    // Top up the escrow from the buyer's original offer to the counter price
+    let total_price = ctx.accounts.offer.counter_amount;
+    let top_up = ctx.accounts.offer.counter_top_up()?;
+    let mint = payment_mint(ctx.accounts.offer.payment_mint, &ctx.accounts.payment_mint)?;
+    let escrow = escrow_account(&ctx.accounts.offer.to_account_info(), &ctx.accounts.payment_escrow, mint)?;
+    wallet_source(
+        &ctx.accounts.buyer,
+        &ctx.accounts.buyer_payment_account,
+        mint,
+        &ctx.accounts.system_program,
+        &ctx.accounts.token_program,
+    )?.pay(&escrow, top_up)?;
+    ctx.accounts.offer.amount = total_price;
+
+    let royalty_config = &ctx.accounts.royalty_config;
+
+    // Calculate royalty distributions
+    let split = royalty_config.split(total_price)?;
+
+    // Pay everyone out of the escrowed offer funds
+    pay_out_offer(
+        &ctx.accounts.offer,
+        mint,
+        &ctx.accounts.payment_escrow,
+        ctx.accounts.seller.to_account_info(),
+        &ctx.accounts.seller_payment_account,
+        &split,
+        ctx.remaining_accounts,
+        ctx.accounts.buyer.to_account_info(),
+        &ctx.accounts.token_program,
+    )?;
+    ctx.accounts.royalty_stats.record_sale(total_price, &split)?;
+
+    // Transfer ticket to buyer and close the escrow
+    let listing = &mut ctx.accounts.listing;
+    release_listing_escrow(
+        listing,
+        &ctx.accounts.escrow_token_account,
+        &ctx.accounts.buyer_token_account,
+        ctx.accounts.seller.to_account_info(),
+        &ctx.accounts.token_program,
+    )?;
+
+    // Mark listing as sold; competing offers can now be refunded
+    listing.status = ListingStatus::Sold;
+    ctx.accounts.offer.status = OfferStatus::Accepted;
+
+    emit!(OfferAccepted {
+        offer: ctx.accounts.offer.key(),
+        listing: listing.key(),
+        buyer: ctx.accounts.buyer.key(),
+        seller: listing.seller,
+        amount: total_price,
+    });
+    emit!(TicketSold {
+        ticket_mint: listing.ticket_mint,
+        event: listing.event,
+        seller: listing.seller,
+        buyer: ctx.accounts.buyer.key(),
+        kind: SaleKind::Offer,
+        price: total_price,
+        payment_mint: listing.payment_mint,
+        royalties: split.royalties,
+        seller_amount: split.seller_amount,
+    });
+
+    Ok(())
+}
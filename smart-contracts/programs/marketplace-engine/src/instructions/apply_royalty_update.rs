@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::errors::*;
+
+/// Permissionless: swaps in a queued royalty update once its time-lock has expired.
+#[derive(Accounts)]
+pub struct ApplyRoyaltyUpdate<'info> {
+    #[account(
+        mut,
+        seeds = [b"royalty_config", royalty_config.event.as_ref()],
+        bump = royalty_config.bump
+    )]
+    pub royalty_config: Account<'info, RoyaltyConfig>,
+}
+
+pub fn handler(ctx: Context<ApplyRoyaltyUpdate>) -> Result<()> {
+    let royalty_config = &mut ctx.accounts.royalty_config;
+    let clock = Clock::get()?;
+    
+    let pending = royalty_config.pending_update
+        .take()
+        .ok_or(MarketplaceError::NoPendingRoyaltyUpdate)?;
+    require!(clock.unix_timestamp >= pending.effective_at, MarketplaceError::RoyaltyUpdateTimelocked);
+    
+    let old_terms = royalty_config.terms();
+    royalty_config.set_terms(&pending.terms);
+    royalty_config.version = royalty_config.version
+        .checked_add(1)
+        .ok_or(MarketplaceError::ArithmeticOverflow)?;
+    
+    emit!(RoyaltyConfigUpdated {
+        royalty_config: royalty_config.key(),
+        version: royalty_config.version,
+        old_terms,
+        new_terms: pending.terms,
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,195 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::{self, CloseAccount, Mint, Token, TokenAccount, Transfer};
+
+use ticket_nft_core::state::Event;
+
+use crate::state::*;
+use crate::errors::*;
+use crate::payment::*;
+
+/// Remaining accounts: for each ticket, in bundle order, `[escrow, buyer_token_account]`; then for
+/// each of `BundleListing::events()`, `[event, royalty_config, royalty_stats]` followed by one
+/// account per royalty recipient of that config (see `pay_royalties`)
+#[derive(Accounts)]
+pub struct BuyBundle<'info> {
+    #[account(
+        mut,
+        seeds = [b"bundle", bundle.seller.as_ref(), &bundle.bundle_id.to_le_bytes()],
+        bump = bundle.bump,
+        close = seller,
+        constraint = bundle.status == ListingStatus::Active @ MarketplaceError::ListingNotActive
+    )]
+    pub bundle: Account<'info, BundleListing>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    /// Seller's account to receive payment and the bundle and escrow rent
+    #[account(
+        mut,
+        constraint = seller.key() == bundle.seller
+    )]
+    pub seller: SystemAccount<'info>,
+
+    /// SPL mint the bundle is priced in; omitted for SOL bundles
+    pub payment_mint: Option<Account<'info, Mint>>,
+
+    /// Buyer's token account paying for a token-priced bundle
+    #[account(mut)]
+    pub buyer_payment_account: Option<Account<'info, TokenAccount>>,
+
+    /// Seller's associated token account receiving a token-priced sale
+    #[account(mut)]
+    pub seller_payment_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Buy every ticket in a bundle at once. The price is divided between the bundle's events by
+/// face value and each share is split under that event's own RoyaltyConfig.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, BuyBundle<'info>>) -> Result<()> {
+    let bundle = &ctx.accounts.bundle;
+    let clock = Clock::get()?;
+
+    if let Some(expires) = bundle.expires_at {
+        require!(expires > clock.unix_timestamp, MarketplaceError::ListingExpired);
+    }
+
+    let ticket_count = bundle.tickets.len();
+    require!(
+        ctx.remaining_accounts.len() >= ticket_count * 2,
+        MarketplaceError::BundleAccountMismatch
+    );
+    let (ticket_accounts, mut royalty_accounts) = ctx.remaining_accounts.split_at(ticket_count * 2);
+
+    // Buyer pays each event's royalty recipients directly, then the seller in one transfer
+    let mint = payment_mint(bundle.payment_mint, &ctx.accounts.payment_mint)?;
+    let source = wallet_source(
+        &ctx.accounts.buyer,
+        &ctx.accounts.buyer_payment_account,
+        mint,
+        &ctx.accounts.system_program,
+        &ctx.accounts.token_program,
+    )?;
+
+    let mut royalties = Vec::new();
+    let mut seller_amount: u64 = 0;
+    for (event, share) in bundle.event_shares()? {
+        require!(royalty_accounts.len() >= 3, MarketplaceError::BundleAccountMismatch);
+
+        // Nothing in the bundle sells once any of its events is cancelled
+        let event_account = Box::new(Account::<Event>::try_from(&royalty_accounts[0])?);
+        require_keys_eq!(event_account.key(), event, MarketplaceError::EventMismatch);
+        require!(!event_account.cancelled, MarketplaceError::EventCancelled);
+
+        let royalty_config = Account::<RoyaltyConfig>::try_from(&royalty_accounts[1])?;
+        require_keys_eq!(royalty_config.event, event, MarketplaceError::RoyaltyConfigMismatch);
+        let mut royalty_stats = Account::<RoyaltyStats>::try_from(&royalty_accounts[2])?;
+        require_keys_eq!(royalty_stats.event, event, MarketplaceError::BundleAccountMismatch);
+
+        let split = royalty_config.split(share)?;
+        let recipients = &royalty_accounts[3..];
+        pay_royalties(&source, &split, recipients, mint)?;
+        royalty_accounts = &recipients[split.royalties.len()..];
+
+        royalty_stats.record_sale(share, &split)?;
+        royalty_stats.exit(ctx.program_id)?;
+
+        seller_amount = seller_amount
+            .checked_add(split.seller_amount)
+            .ok_or(MarketplaceError::ArithmeticOverflow)?;
+        royalties.extend(split.royalties);
+    }
+
+    source.pay(
+        &payout_account(&ctx.accounts.seller.to_account_info(), &ctx.accounts.seller_payment_account, mint)?,
+        seller_amount,
+    )?;
+
+    // Hand over every ticket and close the escrows
+    for (ticket, accounts) in bundle.tickets.iter().zip(ticket_accounts.chunks_exact(2)) {
+        release_bundle_ticket(
+            bundle,
+            ticket,
+            &accounts[0],
+            &accounts[1],
+            ctx.accounts.buyer.key(),
+            ctx.accounts.seller.to_account_info(),
+            &ctx.accounts.token_program,
+        )?;
+    }
+
+    emit!(BundleSold {
+        bundle: bundle.key(),
+        seller: bundle.seller,
+        buyer: ctx.accounts.buyer.key(),
+        ticket_mints: bundle.tickets.iter().map(|ticket| ticket.ticket_mint).collect(),
+        price: bundle.price,
+        payment_mint: bundle.payment_mint,
+        royalties,
+        seller_amount,
+    });
+
+    // Mark bundle as sold; the account closes to the seller
+    ctx.accounts.bundle.status = ListingStatus::Sold;
+
+    Ok(())
+}
+
+/// Send one escrowed ticket to `recipient_token_account`, which must hold the ticket's mint for
+/// `recipient`, and close the bundle's escrow for it, rent to `seller`. Callers close the bundle
+/// itself with `close = seller`.
+pub fn release_bundle_ticket<'info>(
+    bundle: &Account<'info, BundleListing>,
+    ticket: &BundleTicket,
+    escrow: &'info AccountInfo<'info>,
+    recipient_token_account: &'info AccountInfo<'info>,
+    recipient: Pubkey,
+    seller: AccountInfo<'info>,
+    token_program: &Program<'info, Token>,
+) -> Result<()> {
+    require_keys_eq!(
+        escrow.key(),
+        get_associated_token_address(&bundle.key(), &ticket.ticket_mint),
+        MarketplaceError::BundleAccountMismatch
+    );
+    let recipient_token = Account::<TokenAccount>::try_from(recipient_token_account)?;
+    require!(
+        recipient_token.mint == ticket.ticket_mint && recipient_token.owner == recipient,
+        MarketplaceError::BundleAccountMismatch
+    );
+
+    let bundle_id = bundle.bundle_id.to_le_bytes();
+    let seeds = &[
+        b"bundle",
+        bundle.seller.as_ref(),
+        bundle_id.as_ref(),
+        &[bundle.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            Transfer {
+                from: escrow.clone(),
+                to: recipient_token_account.clone(),
+                authority: bundle.to_account_info(),
+            },
+            signer,
+        ),
+        1,
+    )?;
+
+    token::close_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        CloseAccount {
+            account: escrow.clone(),
+            destination: seller,
+            authority: bundle.to_account_info(),
+        },
+        signer,
+    ))
+}
@@ -0,0 +1,161 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use ticket_nft_core::state::Event;
+
+use crate::state::*;
+use crate::errors::*;
+use crate::payment::*;
+
+use super::settle_auction::release_auction_escrow;
+
+/// Remaining accounts: one per royalty recipient, in RoyaltyConfig order (see `pay_royalties`)
+#[derive(Accounts)]
+pub struct BuyDutch<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction", auction.ticket_mint.as_ref()],
+        bump = auction.bump,
+        close = seller
+    )]
+    pub auction: Account<'info, Auction>,
+    
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    
+    /// Seller receives the proceeds and the reclaimed rent
+    #[account(
+        mut,
+        constraint = seller.key() == auction.seller
+    )]
+    pub seller: SystemAccount<'info>,
+    
+    /// Buyer's token account to receive ticket
+    #[account(
+        mut,
+        constraint = buyer_token_account.mint == auction.ticket_mint,
+        constraint = buyer_token_account.owner == buyer.key()
+    )]
+    pub buyer_token_account: Account<'info, TokenAccount>,
+    
+    /// Escrow token account holding the ticket
+    #[account(
+        mut,
+        seeds = [b"auction_escrow", auction.key().as_ref()],
+        bump,
+        constraint = escrow_token_account.amount == 1
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+    
+    /// Auctioned ticket's event; nothing settles once it's cancelled
+    #[account(
+        constraint = event.key() == auction.event @ MarketplaceError::EventMismatch,
+        constraint = !event.cancelled @ MarketplaceError::EventCancelled
+    )]
+    pub event: Box<Account<'info, Event>>,
+    
+    /// Royalty configuration for the auctioned ticket's event
+    #[account(
+        seeds = [b"royalty_config", royalty_config.event.as_ref()],
+        bump = royalty_config.bump,
+        constraint = royalty_config.event == auction.event @ MarketplaceError::RoyaltyConfigMismatch
+    )]
+    pub royalty_config: Account<'info, RoyaltyConfig>,
+    
+    /// Resale totals for the event, updated with this sale
+    #[account(
+        mut,
+        seeds = [b"royalty_stats", auction.event.as_ref()],
+        bump = royalty_stats.bump
+    )]
+    pub royalty_stats: Account<'info, RoyaltyStats>,
+    
+    /// SPL mint of a token auction
+    pub payment_mint: Option<Account<'info, Mint>>,
+    
+    /// Token escrow of a token auction, closed to the seller
+    #[account(
+        mut,
+        seeds = [b"payment_escrow", auction.key().as_ref()],
+        bump
+    )]
+    pub payment_escrow: Option<Account<'info, TokenAccount>>,
+    
+    /// Buyer's token account paying for a token auction
+    #[account(mut)]
+    pub buyer_payment_account: Option<Account<'info, TokenAccount>>,
+    
+    /// Seller's associated token account receiving a token sale
+    #[account(mut)]
+    pub seller_payment_account: Option<Account<'info, TokenAccount>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Buy the ticket from a Dutch auction at the current decayed price
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, BuyDutch<'info>>) -> Result<()> {
+    let clock = Clock::get()?;
+    let auction = &ctx.accounts.auction;
+    
+    require!(auction.status == AuctionStatus::Active, MarketplaceError::AuctionNotActive);
+    require!(clock.unix_timestamp < auction.end_time, MarketplaceError::AuctionEnded);
+    
+    let price = auction.dutch_price(clock.unix_timestamp)?;
+    require!(price <= auction.price_cap, MarketplaceError::PriceExceedsCap);
+    
+    let split = ctx.accounts.royalty_config.split(price)?;
+    
+    // Pay seller and royalty recipients directly from the buyer
+    let mint = payment_mint(auction.payment_mint, &ctx.accounts.payment_mint)?;
+    let source = wallet_source(
+        &ctx.accounts.buyer,
+        &ctx.accounts.buyer_payment_account,
+        mint,
+        &ctx.accounts.system_program,
+        &ctx.accounts.token_program,
+    )?;
+    source.pay(
+        &payout_account(&ctx.accounts.seller.to_account_info(), &ctx.accounts.seller_payment_account, mint)?,
+        split.seller_amount,
+    )?;
+    pay_royalties(&source, &split, ctx.remaining_accounts, mint)?;
+    ctx.accounts.royalty_stats.record_sale(price, &split)?;
+    
+    // Release the ticket and close the escrow token account
+    release_auction_escrow(
+        auction,
+        &ctx.accounts.escrow_token_account,
+        &ctx.accounts.payment_escrow,
+        &ctx.accounts.buyer_token_account,
+        ctx.accounts.seller.to_account_info(),
+        &ctx.accounts.token_program,
+    )?;
+    
+    let auction = &mut ctx.accounts.auction;
+    auction.current_bid = price;
+    auction.highest_bidder = Some(ctx.accounts.buyer.key());
+    auction.status = AuctionStatus::Ended;
+    
+    emit!(TicketSold {
+        ticket_mint: auction.ticket_mint,
+        event: auction.event,
+        seller: auction.seller,
+        buyer: ctx.accounts.buyer.key(),
+        kind: SaleKind::Auction,
+        price,
+        payment_mint: auction.payment_mint,
+        royalties: split.royalties,
+        seller_amount: split.seller_amount,
+    });
+    emit!(AuctionSettled {
+        auction: auction.key(),
+        ticket_mint: auction.ticket_mint,
+        seller: auction.seller,
+        winner: auction.highest_bidder,
+        price,
+        refunded: 0,
+    });
+    
+    Ok(())
+}
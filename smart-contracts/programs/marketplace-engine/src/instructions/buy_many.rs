@@ -0,0 +1,177 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use ticket_nft_core::state::Event;
+
+use crate::state::*;
+use crate::errors::*;
+use crate::payment::*;
+
+use super::buy_ticket::release_listing_escrow;
+
+/// Most listings `buy_many` settles in one instruction, keeping its compute bounded
+pub const MAX_CART_SIZE: usize = 5;
+
+/// Remaining accounts: for each listing `[listing, seller, escrow, buyer_token_account, event,
+/// royalty_config, royalty_stats]`, plus the seller's associated token account for token carts,
+/// followed by one account per royalty recipient of that config (see `pay_royalties`)
+#[derive(Accounts)]
+pub struct BuyMany<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    /// SPL mint every listing in the cart is priced in; omitted for SOL carts
+    pub payment_mint: Option<Account<'info, Mint>>,
+
+    /// Buyer's token account paying for a token cart
+    #[account(mut)]
+    pub buyer_payment_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Buy up to `MAX_CART_SIZE` listings in one transaction, each settled like
+/// `buy_ticket`. Without `allow_partial`, any listing that's no longer for sale, or whose event
+/// has been cancelled, fails the whole purchase; with it, such listings are skipped as long as
+/// at least one is bought.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, BuyMany<'info>>,
+    listing_count: u8,
+    allow_partial: bool,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let listing_count = listing_count as usize;
+
+    require!(
+        listing_count > 0 && listing_count <= MAX_CART_SIZE,
+        MarketplaceError::InvalidCart
+    );
+
+    let mint = ctx.accounts.payment_mint.as_ref();
+    let source = wallet_source(
+        &ctx.accounts.buyer,
+        &ctx.accounts.buyer_payment_account,
+        mint,
+        &ctx.accounts.system_program,
+        &ctx.accounts.token_program,
+    )?;
+    let group_len = if mint.is_some() { 8 } else { 7 };
+
+    let mut remaining = ctx.remaining_accounts;
+    let mut bought = Vec::with_capacity(listing_count);
+    let mut skipped = Vec::new();
+    let mut total_price: u64 = 0;
+
+    for _ in 0..listing_count {
+        require!(remaining.len() >= group_len, MarketplaceError::CartAccountMismatch);
+        let (group, rest) = remaining.split_at(group_len);
+        let royalty_config = Account::<RoyaltyConfig>::try_from(&group[5])?;
+        require!(
+            rest.len() >= royalty_config.recipients.len(),
+            MarketplaceError::RoyaltyRecipientMismatch
+        );
+        let (recipients, rest) = rest.split_at(royalty_config.recipients.len());
+        remaining = rest;
+
+        // Availability is settled before anything moves, so a skip leaves no partial transfer
+        let listing_info = &group[0];
+        if !listing_for_sale(listing_info, clock.unix_timestamp)? {
+            require!(allow_partial, MarketplaceError::ListingNotActive);
+            skipped.push(listing_info.key());
+            continue;
+        }
+
+        let listing = Account::<Listing>::try_from(listing_info)?;
+        let event = Box::new(Account::<Event>::try_from(&group[4])?);
+        require_keys_eq!(event.key(), listing.event, MarketplaceError::EventMismatch);
+        if event.cancelled {
+            require!(allow_partial, MarketplaceError::EventCancelled);
+            skipped.push(listing.key());
+            continue;
+        }
+
+        let seller = &group[1];
+        require_keys_eq!(seller.key(), listing.seller, MarketplaceError::CartAccountMismatch);
+        require!(
+            listing.payment_mint == mint.map(|mint| mint.key()),
+            MarketplaceError::PaymentMintMismatch
+        );
+
+        let escrow = Account::<TokenAccount>::try_from(&group[2])?;
+        require!(
+            escrow.owner == listing.key() && escrow.mint == listing.ticket_mint && escrow.amount == 1,
+            MarketplaceError::CartAccountMismatch
+        );
+        let buyer_token_account = Account::<TokenAccount>::try_from(&group[3])?;
+        require!(
+            buyer_token_account.owner == ctx.accounts.buyer.key()
+                && buyer_token_account.mint == listing.ticket_mint,
+            MarketplaceError::CartAccountMismatch
+        );
+        require_keys_eq!(royalty_config.event, listing.event, MarketplaceError::RoyaltyConfigMismatch);
+        let mut royalty_stats = Account::<RoyaltyStats>::try_from(&group[6])?;
+        require_keys_eq!(royalty_stats.event, listing.event, MarketplaceError::CartAccountMismatch);
+        let seller_payment_account = match mint {
+            Some(_) => Some(Account::<TokenAccount>::try_from(&group[7])?),
+            None => None,
+        };
+
+        // Buyer pays the seller and every royalty recipient directly
+        let split = royalty_config.split(listing.price)?;
+        source.pay(&payout_account(seller, &seller_payment_account, mint)?, split.seller_amount)?;
+        pay_royalties(&source, &split, recipients, mint)?;
+
+        royalty_stats.record_sale(listing.price, &split)?;
+        royalty_stats.exit(ctx.program_id)?;
+
+        // Transfer ticket to buyer, then close the escrow and the listing to the seller
+        release_listing_escrow(
+            &listing,
+            &escrow,
+            &buyer_token_account,
+            seller.clone(),
+            &ctx.accounts.token_program,
+        )?;
+        listing.close(seller.clone())?;
+
+        total_price = total_price
+            .checked_add(listing.price)
+            .ok_or(MarketplaceError::ArithmeticOverflow)?;
+        bought.push(listing.key());
+
+        emit!(TicketSold {
+            ticket_mint: listing.ticket_mint,
+            event: listing.event,
+            seller: listing.seller,
+            buyer: ctx.accounts.buyer.key(),
+            kind: SaleKind::Listing,
+            price: listing.price,
+            payment_mint: listing.payment_mint,
+            royalties: split.royalties,
+            seller_amount: split.seller_amount,
+        });
+    }
+
+    require!(!bought.is_empty(), MarketplaceError::NothingPurchased);
+
+    emit!(CartPurchased {
+        buyer: ctx.accounts.buyer.key(),
+        bought,
+        skipped,
+        total_price,
+    });
+
+    Ok(())
+}
+
+/// Whether `listing` can still be bought: a live, unexpired listing. Sold and cancelled
+/// listings are closed, so anything this program no longer owns is gone.
+fn listing_for_sale(listing: &AccountInfo, now: i64) -> Result<bool> {
+    if listing.owner != &crate::ID || listing.data_is_empty() {
+        return Ok(false);
+    }
+
+    let listing = Listing::try_deserialize(&mut &listing.data.borrow()[..])?;
+    Ok(listing.status == ListingStatus::Active && !listing.is_expired(now))
+}
@@ -1,15 +1,20 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token::{self, CloseAccount, Mint, Token, TokenAccount, Transfer};
+
+use ticket_nft_core::state::Event;
 
 use crate::state::*;
 use crate::errors::*;
+use crate::payment::*;
 
+/// Remaining accounts: one per royalty recipient, in RoyaltyConfig order (see `pay_royalties`)
 #[derive(Accounts)]
 pub struct BuyTicket<'info> {
     #[account(
         mut,
         seeds = [b"listing", listing.ticket_mint.as_ref()],
         bump = listing.bump,
+        close = seller,
         constraint = listing.status == ListingStatus::Active @ MarketplaceError::ListingNotActive
     )]
     pub listing: Account<'info, Listing>,
@@ -17,7 +22,7 @@ pub struct BuyTicket<'info> {
     #[account(mut)]
     pub buyer: Signer<'info>,
     
-    /// Seller's account to receive payment
+    /// Seller's account to receive payment and the listing and escrow rent
     #[account(
         mut,
         constraint = seller.key() == listing.seller
@@ -41,134 +46,111 @@ pub struct BuyTicket<'info> {
     )]
     pub escrow_token_account: Account<'info, TokenAccount>,
     
-    /// Royalty configuration
+    /// Listed ticket's event; nothing settles once it's cancelled
     #[account(
-        seeds = [b"royalty_config", royalty_config.event_mint.as_ref()],
-        bump = royalty_config.bump
+        constraint = event.key() == listing.event @ MarketplaceError::EventMismatch,
+        constraint = !event.cancelled @ MarketplaceError::EventCancelled
     )]
-    pub royalty_config: Account<'info, RoyaltyConfig>,
+    pub event: Box<Account<'info, Event>>,
     
-    /// Artist wallet for royalty payment
+    /// Royalty configuration of the listed ticket's event; any other event's config is rejected
     #[account(
-        mut,
-        constraint = artist_wallet.key() == royalty_config.artist_wallet
+        seeds = [b"royalty_config", royalty_config.event.as_ref()],
+        bump = royalty_config.bump,
+        constraint = royalty_config.event == listing.event @ MarketplaceError::RoyaltyConfigMismatch
     )]
-    pub artist_wallet: SystemAccount<'info>,
+    pub royalty_config: Account<'info, RoyaltyConfig>,
     
-    /// Venue wallet for royalty payment
+    /// Resale totals for the event, updated with this sale
     #[account(
         mut,
-        constraint = venue_wallet.key() == royalty_config.venue_wallet
+        seeds = [b"royalty_stats", listing.event.as_ref()],
+        bump = royalty_stats.bump
     )]
-    pub venue_wallet: SystemAccount<'info>,
+    pub royalty_stats: Account<'info, RoyaltyStats>,
     
-    /// Platform wallet for fees
-    #[account(
-        mut,
-        constraint = platform_wallet.key() == royalty_config.platform_wallet
-    )]
-    pub platform_wallet: SystemAccount<'info>,
+    /// SPL mint the listing is priced in; omitted for SOL listings
+    pub payment_mint: Option<Account<'info, Mint>>,
+    
+    /// Buyer's token account paying for a token-priced listing
+    #[account(mut)]
+    pub buyer_payment_account: Option<Account<'info, TokenAccount>>,
+    
+    /// Seller's associated token account receiving a token-priced sale
+    #[account(mut)]
+    pub seller_payment_account: Option<Account<'info, TokenAccount>>,
     
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<BuyTicket>) -> Result<()> {
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, BuyTicket<'info>>) -> Result<()> {
     let listing = &mut ctx.accounts.listing;
     let royalty_config = &ctx.accounts.royalty_config;
     let clock = Clock::get()?;
     
     // Check listing hasn't expired
-    if let Some(expires) = listing.expires_at {
-        require!(expires > clock.unix_timestamp, MarketplaceError::ListingExpired);
-    }
+    require!(!listing.is_expired(clock.unix_timestamp), MarketplaceError::ListingExpired);
     
     let total_price = listing.price;
     
-    // Calculate royalty distributions
-    let artist_royalty = total_price
-        .checked_mul(royalty_config.artist_percentage as u64)
-        .ok_or(MarketplaceError::ArithmeticOverflow)?
-        .checked_div(10000)
-        .ok_or(MarketplaceError::ArithmeticOverflow)?;
-    
-    let venue_royalty = total_price
-        .checked_mul(royalty_config.venue_percentage as u64)
-        .ok_or(MarketplaceError::ArithmeticOverflow)?
-        .checked_div(10000)
-        .ok_or(MarketplaceError::ArithmeticOverflow)?;
-    
-    let platform_fee = total_price
-        .checked_mul(royalty_config.platform_percentage as u64)
-        .ok_or(MarketplaceError::ArithmeticOverflow)?
-        .checked_div(10000)
-        .ok_or(MarketplaceError::ArithmeticOverflow)?;
-    
-    let seller_amount = total_price
-        .checked_sub(artist_royalty)
-        .ok_or(MarketplaceError::ArithmeticOverflow)?
-        .checked_sub(venue_royalty)
-        .ok_or(MarketplaceError::ArithmeticOverflow)?
-        .checked_sub(platform_fee)
-        .ok_or(MarketplaceError::ArithmeticOverflow)?;
-    
-    // Transfer payments
-    // Pay seller
-    anchor_lang::system_program::transfer(
-        CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
-            anchor_lang::system_program::Transfer {
-                from: ctx.accounts.buyer.to_account_info(),
-                to: ctx.accounts.seller.to_account_info(),
-            },
-        ),
-        seller_amount,
+    // Calculate royalty distributions; every recipient's cut (platform fee included)
+    // is withheld from the seller and paid out below
+    let split = royalty_config.split(total_price)?;
+    
+    // Buyer pays the seller and every royalty recipient directly, in SOL or the listing's mint
+    let mint = payment_mint(listing.payment_mint, &ctx.accounts.payment_mint)?;
+    let source = wallet_source(
+        &ctx.accounts.buyer,
+        &ctx.accounts.buyer_payment_account,
+        mint,
+        &ctx.accounts.system_program,
+        &ctx.accounts.token_program,
     )?;
     
-    // Pay artist royalty
-    if artist_royalty > 0 {
-        anchor_lang::system_program::transfer(
-            CpiContext::new(
-                ctx.accounts.system_program.to_account_info(),
-                anchor_lang::system_program::Transfer {
-                    from: ctx.accounts.buyer.to_account_info(),
-                    to: ctx.accounts.artist_wallet.to_account_info(),
-                },
-            ),
-            artist_royalty,
-        )?;
-    }
-    
-    // Pay venue royalty
-    if venue_royalty > 0 {
-        anchor_lang::system_program::transfer(
-            CpiContext::new(
-                ctx.accounts.system_program.to_account_info(),
-                anchor_lang::system_program::Transfer {
-                    from: ctx.accounts.buyer.to_account_info(),
-                    to: ctx.accounts.venue_wallet.to_account_info(),
-                },
-            ),
-            venue_royalty,
-        )?;
-    }
-    
-    // Pay platform fee
-    if platform_fee > 0 {
-        anchor_lang::system_program::transfer(
-            CpiContext::new(
-                ctx.accounts.system_program.to_account_info(),
-                anchor_lang::system_program::Transfer {
-                    from: ctx.accounts.buyer.to_account_info(),
-                    to: ctx.accounts.platform_wallet.to_account_info(),
-                },
-            ),
-            platform_fee,
-        )?;
-    }
-    
-    // Transfer ticket to buyer
-    let listing_key = listing.key();
+    source.pay(
+        &payout_account(&ctx.accounts.seller.to_account_info(), &ctx.accounts.seller_payment_account, mint)?,
+        split.seller_amount,
+    )?;
+    pay_royalties(&source, &split, ctx.remaining_accounts, mint)?;
+    ctx.accounts.royalty_stats.record_sale(total_price, &split)?;
+    
+    // Transfer ticket to buyer and close the escrow
+    release_listing_escrow(
+        listing,
+        &ctx.accounts.escrow_token_account,
+        &ctx.accounts.buyer_token_account,
+        ctx.accounts.seller.to_account_info(),
+        &ctx.accounts.token_program,
+    )?;
+    
+    // Mark listing as sold; the account closes to the seller so the ticket can be relisted
+    listing.status = ListingStatus::Sold;
+    
+    emit!(TicketSold {
+        ticket_mint: listing.ticket_mint,
+        event: listing.event,
+        seller: listing.seller,
+        buyer: ctx.accounts.buyer.key(),
+        kind: SaleKind::Listing,
+        price: total_price,
+        payment_mint: listing.payment_mint,
+        royalties: split.royalties,
+        seller_amount: split.seller_amount,
+    });
+    
+    Ok(())
+}
+
+/// Send the escrowed ticket to `recipient_token_account` and close the listing's escrow token
+/// account, rent to `seller`. Callers close the listing itself with `close = seller`.
+pub fn release_listing_escrow<'info>(
+    listing: &Account<'info, Listing>,
+    escrow_token_account: &Account<'info, TokenAccount>,
+    recipient_token_account: &Account<'info, TokenAccount>,
+    seller: AccountInfo<'info>,
+    token_program: &Program<'info, Token>,
+) -> Result<()> {
     let seeds = &[
         b"listing",
         listing.ticket_mint.as_ref(),
@@ -176,23 +158,26 @@ pub fn handler(ctx: Context<BuyTicket>) -> Result<()> {
     ];
     let signer = &[&seeds[..]];
     
-    let cpi_accounts = Transfer {
-        from: ctx.accounts.escrow_token_account.to_account_info(),
-        to: ctx.accounts.buyer_token_account.to_account_info(),
-        authority: listing.to_account_info(),
-    };
-    let cpi_program = ctx.accounts.token_program.to_account_info();
-    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
-    token::transfer(cpi_ctx, 1)?;
-    
-    // Mark listing as sold
-    listing.status = ListingStatus::Sold;
-    
-    msg!("Ticket sold! Artist: {} SOL, Venue: {} SOL, Platform: {} SOL, Seller: {} SOL", 
-         artist_royalty as f64 / 1_000_000_000.0,
-         venue_royalty as f64 / 1_000_000_000.0,
-         platform_fee as f64 / 1_000_000_000.0,
-         seller_amount as f64 / 1_000_000_000.0);
+    token::transfer(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            Transfer {
+                from: escrow_token_account.to_account_info(),
+                to: recipient_token_account.to_account_info(),
+                authority: listing.to_account_info(),
+            },
+            signer,
+        ),
+        1,
+    )?;
     
-    Ok(())
+    token::close_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        CloseAccount {
+            account: escrow_token_account.to_account_info(),
+            destination: seller,
+            authority: listing.to_account_info(),
+        },
+        signer,
+    ))
 }
@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+use crate::state::*;
+use crate::errors::*;
+
+use super::settle_auction::release_auction_escrow;
+
+#[derive(Accounts)]
+pub struct CancelAuction<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction", auction.ticket_mint.as_ref()],
+        bump = auction.bump,
+        close = seller,
+        constraint = auction.seller == seller.key() @ MarketplaceError::Unauthorized
+    )]
+    pub auction: Account<'info, Auction>,
+    
+    #[account(mut)]
+    pub seller: Signer<'info>,
+    
+    /// Seller's token account to receive ticket back
+    #[account(
+        mut,
+        constraint = seller_token_account.mint == auction.ticket_mint,
+        constraint = seller_token_account.owner == seller.key()
+    )]
+    pub seller_token_account: Account<'info, TokenAccount>,
+    
+    /// Escrow token account holding the ticket
+    #[account(
+        mut,
+        seeds = [b"auction_escrow", auction.key().as_ref()],
+        bump,
+        constraint = escrow_token_account.amount == 1
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+    
+    /// Empty token escrow of a token auction, closed to the seller
+    #[account(
+        mut,
+        seeds = [b"payment_escrow", auction.key().as_ref()],
+        bump
+    )]
+    pub payment_escrow: Option<Account<'info, TokenAccount>>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+/// Cancel an auction and take the ticket back; only allowed before the first bid
+pub fn handler(ctx: Context<CancelAuction>) -> Result<()> {
+    let auction = &ctx.accounts.auction;
+    
+    require!(auction.status == AuctionStatus::Active, MarketplaceError::AuctionNotActive);
+    require!(
+        auction.highest_bidder.is_none() && auction.sealed_bid_count == 0,
+        MarketplaceError::AuctionHasBids
+    );
+    
+    release_auction_escrow(
+        auction,
+        &ctx.accounts.escrow_token_account,
+        &ctx.accounts.payment_escrow,
+        &ctx.accounts.seller_token_account,
+        ctx.accounts.seller.to_account_info(),
+        &ctx.accounts.token_program,
+    )?;
+    
+    let auction = &mut ctx.accounts.auction;
+    auction.status = AuctionStatus::Cancelled;
+    
+    emit!(AuctionCancelled {
+        auction: auction.key(),
+        ticket_mint: auction.ticket_mint,
+        seller: auction.seller,
+        refunded_bidder: None,
+        refunded: 0,
+    });
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Token;
+
+use crate::state::*;
+use crate::errors::*;
+
+use super::buy_bundle::release_bundle_ticket;
+
+/// Remaining accounts: for each ticket, in bundle order, `[escrow, seller_token_account]`
+#[derive(Accounts)]
+pub struct CancelBundleListing<'info> {
+    #[account(
+        mut,
+        seeds = [b"bundle", bundle.seller.as_ref(), &bundle.bundle_id.to_le_bytes()],
+        bump = bundle.bump,
+        close = seller,
+        constraint = bundle.seller == seller.key() @ MarketplaceError::Unauthorized,
+        constraint = bundle.status == ListingStatus::Active @ MarketplaceError::ListingNotActive
+    )]
+    pub bundle: Account<'info, BundleListing>,
+
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, CancelBundleListing<'info>>) -> Result<()> {
+    let bundle = &ctx.accounts.bundle;
+    require!(
+        ctx.remaining_accounts.len() >= bundle.tickets.len() * 2,
+        MarketplaceError::BundleAccountMismatch
+    );
+
+    // Return every ticket to the seller and close the escrows
+    for (ticket, accounts) in bundle.tickets.iter().zip(ctx.remaining_accounts.chunks_exact(2)) {
+        release_bundle_ticket(
+            bundle,
+            ticket,
+            &accounts[0],
+            &accounts[1],
+            bundle.seller,
+            ctx.accounts.seller.to_account_info(),
+            &ctx.accounts.token_program,
+        )?;
+    }
+
+    emit!(BundleCancelled {
+        bundle: bundle.key(),
+        seller: bundle.seller,
+    });
+
+    // Mark as cancelled; the account closes to the seller
+    ctx.accounts.bundle.status = ListingStatus::Cancelled;
+
+    Ok(())
+}
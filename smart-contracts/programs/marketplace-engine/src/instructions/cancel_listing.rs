@@ -1,15 +1,18 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token::{Token, TokenAccount};
 
 use crate::state::*;
 use crate::errors::*;
 
+use super::buy_ticket::release_listing_escrow;
+
 #[derive(Accounts)]
 pub struct CancelListing<'info> {
     #[account(
         mut,
         seeds = [b"listing", listing.ticket_mint.as_ref()],
         bump = listing.bump,
+        close = seller,
         constraint = listing.seller == seller.key() @ MarketplaceError::Unauthorized,
         constraint = listing.status == ListingStatus::Active @ MarketplaceError::ListingNotActive
     )]
@@ -41,28 +44,23 @@ pub struct CancelListing<'info> {
 pub fn handler(ctx: Context<CancelListing>) -> Result<()> {
     let listing = &mut ctx.accounts.listing;
     
-    // Transfer ticket back to seller
-    let listing_key = listing.key();
-    let seeds = &[
-        b"listing",
-        listing.ticket_mint.as_ref(),
-        &[listing.bump],
-    ];
-    let signer = &[&seeds[..]];
-    
-    let cpi_accounts = Transfer {
-        from: ctx.accounts.escrow_token_account.to_account_info(),
-        to: ctx.accounts.seller_token_account.to_account_info(),
-        authority: listing.to_account_info(),
-    };
-    let cpi_program = ctx.accounts.token_program.to_account_info();
-    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
-    token::transfer(cpi_ctx, 1)?;
+    // Transfer ticket back to seller and close the escrow
+    release_listing_escrow(
+        listing,
+        &ctx.accounts.escrow_token_account,
+        &ctx.accounts.seller_token_account,
+        ctx.accounts.seller.to_account_info(),
+        &ctx.accounts.token_program,
+    )?;
     
-    // Mark as cancelled
+    // Mark as cancelled; the account closes to the seller so the ticket can be relisted
     listing.status = ListingStatus::Cancelled;
     
-    msg!("Listing cancelled and ticket returned to seller");
+    emit!(ListingCancelled {
+        listing: listing.key(),
+        ticket_mint: listing.ticket_mint,
+        seller: listing.seller,
+    });
     
     Ok(())
 }
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::errors::*;
+
+/// Authority withdraws a queued royalty update before it is applied.
+#[derive(Accounts)]
+pub struct CancelRoyaltyUpdate<'info> {
+    #[account(
+        mut,
+        seeds = [b"royalty_config", royalty_config.event.as_ref()],
+        bump = royalty_config.bump,
+        has_one = authority @ MarketplaceError::Unauthorized
+    )]
+    pub royalty_config: Account<'info, RoyaltyConfig>,
+    
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<CancelRoyaltyUpdate>) -> Result<()> {
+    let royalty_config = &mut ctx.accounts.royalty_config;
+    
+    let pending = royalty_config.pending_update
+        .take()
+        .ok_or(MarketplaceError::NoPendingRoyaltyUpdate)?;
+    
+    emit!(RoyaltyUpdateCancelled {
+        royalty_config: royalty_config.key(),
+        cancelled_terms: pending.terms,
+    });
+    
+    Ok(())
+}
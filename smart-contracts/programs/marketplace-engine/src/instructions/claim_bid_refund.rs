@@ -0,0 +1,97 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::state::*;
+use crate::errors::*;
+use crate::payment::*;
+
+#[derive(Accounts)]
+pub struct ClaimBidRefund<'info> {
+    #[account(
+        mut,
+        seeds = [b"bid_refund", bid_refund.auction.as_ref(), bidder.key().as_ref(), &bid_refund.auction_nonce.to_le_bytes()],
+        bump = bid_refund.bump,
+        close = rent_payer
+    )]
+    pub bid_refund: Account<'info, BidRefund>,
+    
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    
+    /// Whoever paid the rent of the refund account and its escrow, which goes back to them
+    #[account(
+        mut,
+        constraint = rent_payer.key() == bid_refund.rent_payer
+    )]
+    pub rent_payer: SystemAccount<'info>,
+    
+    /// SPL mint of a token auction
+    pub payment_mint: Option<Account<'info, Mint>>,
+    
+    /// Token escrow of the refund, emptied to the bidder and closed to the rent payer
+    #[account(
+        mut,
+        seeds = [b"payment_escrow", bid_refund.key().as_ref()],
+        bump
+    )]
+    pub payment_escrow: Option<Account<'info, TokenAccount>>,
+    
+    /// Bidder's associated token account receiving a token refund
+    #[account(mut)]
+    pub bidder_payment_account: Option<Account<'info, TokenAccount>>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+/// Claim everything an auction owes the bidder. Refunds are credited here rather than paid to
+/// the bidder directly, so they can be claimed at any time, including while the auction runs.
+pub fn handler(ctx: Context<ClaimBidRefund>) -> Result<()> {
+    let bid_refund = &ctx.accounts.bid_refund;
+    
+    // The refund goes to the bidder and the rent to whoever paid it when the accounts close
+    let claimed = if let Some(mint) = payment_mint(bid_refund.payment_mint, &ctx.accounts.payment_mint)? {
+        let escrow = ctx.accounts.payment_escrow
+            .as_ref()
+            .ok_or(MarketplaceError::InvalidPaymentAccount)?;
+        let auction_nonce = bid_refund.auction_nonce.to_le_bytes();
+        let seeds = &[
+            b"bid_refund",
+            bid_refund.auction.as_ref(),
+            bid_refund.bidder.as_ref(),
+            auction_nonce.as_ref(),
+            &[bid_refund.bump],
+        ];
+        let signer = &[&seeds[..]];
+        
+        let bidder = ctx.accounts.bidder.to_account_info();
+        PaymentSource::Token {
+            from: escrow.to_account_info(),
+            authority: bid_refund.to_account_info(),
+            signer_seeds: signer,
+            mint,
+            token_program: ctx.accounts.token_program.to_account_info(),
+        }.pay(&payout_account(&bidder, &ctx.accounts.bidder_payment_account, Some(mint))?, escrow.amount)?;
+        
+        close_payment_escrow(
+            &ctx.accounts.payment_escrow,
+            ctx.accounts.rent_payer.to_account_info(),
+            bid_refund.to_account_info(),
+            signer,
+            ctx.accounts.token_program.to_account_info(),
+        )?;
+        escrow.amount
+    } else {
+        let rent = Rent::get()?.minimum_balance(BidRefund::LEN);
+        let claimed = bid_refund.to_account_info().lamports().saturating_sub(rent);
+        PaymentSource::Escrow(bid_refund.to_account_info()).pay(&ctx.accounts.bidder, claimed)?;
+        claimed
+    };
+    
+    emit!(BidRefundClaimed {
+        auction: bid_refund.auction,
+        bidder: bid_refund.bidder,
+        amount: claimed,
+    });
+    
+    Ok(())
+}
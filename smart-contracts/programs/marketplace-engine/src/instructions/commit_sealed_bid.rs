@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use ticket_nft_core::state::Event;
+
+use crate::state::*;
+use crate::errors::*;
+use crate::payment::*;
+
+#[derive(Accounts)]
+pub struct CommitSealedBid<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction", auction.ticket_mint.as_ref()],
+        bump = auction.bump
+    )]
+    pub auction: Account<'info, Auction>,
+    
+    #[account(
+        init,
+        payer = bidder,
+        space = SealedBid::LEN,
+        seeds = [b"sealed_bid", auction.key().as_ref(), bidder.key().as_ref(), &auction.nonce.to_le_bytes()],
+        bump
+    )]
+    pub sealed_bid: Account<'info, SealedBid>,
+    
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    
+    /// Ticket's event; nothing new is bid on once it's cancelled
+    #[account(
+        constraint = event.key() == auction.event @ MarketplaceError::EventMismatch,
+        constraint = !event.cancelled @ MarketplaceError::EventCancelled
+    )]
+    pub event: Box<Account<'info, Event>>,
+    
+    /// SPL mint of a token auction
+    pub payment_mint: Option<Account<'info, Mint>>,
+    
+    /// Holds a token deposit until it is revealed or withdrawn
+    #[account(
+        init,
+        payer = bidder,
+        token::mint = payment_mint,
+        token::authority = sealed_bid,
+        seeds = [b"payment_escrow", sealed_bid.key().as_ref()],
+        bump
+    )]
+    pub payment_escrow: Option<Account<'info, TokenAccount>>,
+    
+    /// Bidder's token account funding a token deposit
+    #[account(mut)]
+    pub bidder_payment_account: Option<Account<'info, TokenAccount>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Commit a hidden bid to a sealed auction, escrowing a deposit that must cover it
+pub fn handler(ctx: Context<CommitSealedBid>, commitment: [u8; 32], deposit: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    let auction = &ctx.accounts.auction;
+    
+    require!(auction.status == AuctionStatus::Active, MarketplaceError::AuctionNotActive);
+    require!(clock.unix_timestamp < auction.end_time, MarketplaceError::AuctionEnded);
+    require!(
+        matches!(auction.auction_type, AuctionType::Sealed { .. }),
+        MarketplaceError::InvalidAuctionType
+    );
+    require!(deposit >= auction.starting_bid && deposit > 0, MarketplaceError::BidTooLow);
+    
+    let mint = payment_mint(auction.payment_mint, &ctx.accounts.payment_mint)?;
+    let escrow = escrow_account(&ctx.accounts.sealed_bid.to_account_info(), &ctx.accounts.payment_escrow, mint)?;
+    wallet_source(
+        &ctx.accounts.bidder,
+        &ctx.accounts.bidder_payment_account,
+        mint,
+        &ctx.accounts.system_program,
+        &ctx.accounts.token_program,
+    )?.pay(&escrow, deposit)?;
+    
+    let reveal_end = auction.settlement_time();
+    let sealed_bid = &mut ctx.accounts.sealed_bid;
+    sealed_bid.auction = ctx.accounts.auction.key();
+    sealed_bid.auction_nonce = auction.nonce;
+    sealed_bid.bidder = ctx.accounts.bidder.key();
+    sealed_bid.event = auction.event;
+    sealed_bid.commitment = commitment;
+    sealed_bid.deposit = deposit;
+    sealed_bid.payment_mint = auction.payment_mint;
+    sealed_bid.amount = 0;
+    sealed_bid.revealed = false;
+    sealed_bid.reveal_end = reveal_end;
+    sealed_bid.bump = ctx.bumps.sealed_bid;
+    
+    let auction = &mut ctx.accounts.auction;
+    auction.sealed_bid_count = auction.sealed_bid_count
+        .checked_add(1)
+        .ok_or(MarketplaceError::ArithmeticOverflow)?;
+    
+    emit!(SealedBidCommitted {
+        auction: auction.key(),
+        bidder: ctx.accounts.bidder.key(),
+        deposit,
+    });
+    Ok(())
+}
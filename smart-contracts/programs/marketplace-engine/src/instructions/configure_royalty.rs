@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+
+use ticket_nft_core::state::Event;
+
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct ConfigureRoyalty<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = RoyaltyConfig::LEN,
+        seeds = [b"royalty_config", event.key().as_ref()],
+        bump
+    )]
+    pub royalty_config: Account<'info, RoyaltyConfig>,
+    
+    /// Resale totals for the event, kept up to date by every settlement
+    #[account(
+        init,
+        payer = authority,
+        space = RoyaltyStats::LEN,
+        seeds = [b"royalty_stats", event.key().as_ref()],
+        bump
+    )]
+    pub royalty_stats: Account<'info, RoyaltyStats>,
+    
+    /// Event organizer from ticket-nft-core; nobody else can set up its royalties
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    /// Event the royalties apply to
+    #[account(
+        seeds = [b"event", event.authority.as_ref(), &event.event_id.to_le_bytes()],
+        bump = event.bump,
+        seeds::program = ticket_nft_core::ID,
+        constraint = event.authority == authority.key() @ MarketplaceError::Unauthorized
+    )]
+    pub event: Account<'info, Event>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Configure royalty recipients for an event, e.g. artist 1000 (10%), venue 500 (5%),
+/// platform 100 (1%), with a 20000 (200%) price cap
+pub fn handler(ctx: Context<ConfigureRoyalty>, terms: RoyaltyTerms) -> Result<()> {
+    let royalty_config = &mut ctx.accounts.royalty_config;
+    let clock = Clock::get()?;
+    
+    // Validate the recipient list and that percentages don't exceed 100%
+    terms.validate()?;
+    
+    royalty_config.event = ctx.accounts.event.key();
+    royalty_config.set_terms(&terms);
+    royalty_config.authority = ctx.accounts.authority.key();
+    royalty_config.created_at = clock.unix_timestamp;
+    royalty_config.version = 1;
+    royalty_config.pending_update = None;
+    royalty_config.bump = ctx.bumps.royalty_config;
+    
+    let royalty_stats = &mut ctx.accounts.royalty_stats;
+    royalty_stats.event = royalty_config.event;
+    royalty_stats.total_volume = 0;
+    royalty_stats.sale_count = 0;
+    royalty_stats.highest_price = 0;
+    royalty_stats.total_royalties = 0;
+    royalty_stats.payees = Vec::new();
+    royalty_stats.bump = ctx.bumps.royalty_stats;
+    
+    emit!(RoyaltyConfigured {
+        royalty_config: royalty_config.key(),
+        event: royalty_config.event,
+        authority: royalty_config.authority,
+        terms,
+    });
+    
+    Ok(())
+}
@@ -1,14 +1,151 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use ticket_nft_core::state::Event;
+
 use crate::state::*;
+use crate::errors::*;
+use crate::payment::*;
 
 #[derive(Accounts)]
 pub struct CounterOffer<'info> {
-    #[account(mut)]
+    #[account(
+        mut,
+        seeds = [b"offer", listing.key().as_ref(), offer.buyer.as_ref(), &offer.listing_nonce.to_le_bytes()],
+        bump = offer.bump,
+        constraint = offer.listing_nonce == listing.nonce @ MarketplaceError::StaleOffer
+    )]
     pub offer: Account<'info, Offer>,
-    pub seller: Signer<'info>,
+
+    #[account(
+        seeds = [b"listing", listing.ticket_mint.as_ref()],
+        bump = listing.bump,
+        constraint = listing.status == ListingStatus::Active @ MarketplaceError::ListingNotActive
+    )]
+    pub listing: Account<'info, Listing>,
+
+    /// Seller while the buyer's offer stands, buyer while a counter stands
+    #[account(mut)]
+    pub party: Signer<'info>,
+
+    /// Listed ticket's event; negotiation stops once it's cancelled and the offer is refunded
+    #[account(
+        constraint = event.key() == listing.event @ MarketplaceError::EventMismatch,
+        constraint = !event.cancelled @ MarketplaceError::EventCancelled
+    )]
+    pub event: Box<Account<'info, Event>>,
+
+    /// SPL mint of a token-denominated offer
+    pub payment_mint: Option<Account<'info, Mint>>,
+
+    /// Token escrow of a token-denominated offer
+    #[account(
+        mut,
+        seeds = [b"payment_escrow", offer.key().as_ref()],
+        bump
+    )]
+    pub payment_escrow: Option<Account<'info, TokenAccount>>,
+
+    /// Buyer's token account, topped up from or refunded to when the buyer counters
+    #[account(mut)]
+    pub buyer_payment_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
 }
 
 pub fn handler(ctx: Context<CounterOffer>, new_amount: u64, expires_at: i64) -> Result<()> {
-    msg!("Counter offer: {} SOL", new_amount as f64 / 1_000_000_000.0);
+    let clock = Clock::get()?;
+    let listing = &ctx.accounts.listing;
+    let party = ctx.accounts.party.key();
+
+    {
+        let offer = &ctx.accounts.offer;
+        require!(offer.expires_at > clock.unix_timestamp, MarketplaceError::OfferExpired);
+        require!(offer.rounds < Offer::MAX_ROUNDS, MarketplaceError::TooManyCounterRounds);
+    }
+    require!(!listing.is_expired(clock.unix_timestamp), MarketplaceError::ListingExpired);
+    require!(expires_at > clock.unix_timestamp, MarketplaceError::OfferExpired);
+    require!(new_amount <= listing.price_cap, MarketplaceError::PriceExceedsCap);
+
+    match ctx.accounts.offer.status {
+        OfferStatus::Active => {
+            // Seller asks for more than the buyer's standing offer
+            require!(party == listing.seller, MarketplaceError::Unauthorized);
+            require!(new_amount > ctx.accounts.offer.amount, MarketplaceError::InvalidCounterAmount);
+
+            let offer = &mut ctx.accounts.offer;
+            offer.counter_amount = new_amount;
+            offer.status = OfferStatus::CounterOffered;
+        }
+        OfferStatus::CounterOffered => {
+            // Buyer answers with a revised offer; escrow follows the new amount
+            require!(party == ctx.accounts.offer.buyer, MarketplaceError::Unauthorized);
+            require!(
+                new_amount > 0 && new_amount < ctx.accounts.offer.counter_amount,
+                MarketplaceError::InvalidCounterAmount
+            );
+
+            let escrowed = ctx.accounts.offer.amount;
+            let mint = payment_mint(ctx.accounts.offer.payment_mint, &ctx.accounts.payment_mint)?;
+
+            if new_amount > escrowed {
+                let escrow = escrow_account(&ctx.accounts.offer.to_account_info(), &ctx.accounts.payment_escrow, mint)?;
+                wallet_source(
+                    &ctx.accounts.party,
+                    &ctx.accounts.buyer_payment_account,
+                    mint,
+                    &ctx.accounts.system_program,
+                    &ctx.accounts.token_program,
+                )?.pay(&escrow, new_amount - escrowed)?;
+            } else if new_amount < escrowed {
+                let offer = &ctx.accounts.offer;
+                let listing_nonce = offer.listing_nonce.to_le_bytes();
+                let seeds = &[
+                    b"offer",
+                    offer.listing.as_ref(),
+                    offer.buyer.as_ref(),
+                    listing_nonce.as_ref(),
+                    &[offer.bump],
+                ];
+                let signer = &[&seeds[..]];
+
+                let source = escrow_source(
+                    &offer.to_account_info(),
+                    &ctx.accounts.payment_escrow,
+                    mint,
+                    signer,
+                    ctx.accounts.token_program.to_account_info(),
+                )?;
+                let refund_to = payout_account(
+                    &ctx.accounts.party.to_account_info(),
+                    &ctx.accounts.buyer_payment_account,
+                    mint,
+                )?;
+                source.pay(&refund_to, escrowed - new_amount)?;
+            }
+
+            let offer = &mut ctx.accounts.offer;
+            offer.amount = new_amount;
+            offer.counter_amount = 0;
+            offer.status = OfferStatus::Active;
+        }
+        _ => return err!(MarketplaceError::OfferNotActive),
+    }
+
+    let offer = &mut ctx.accounts.offer;
+    offer.rounds = offer.rounds
+        .checked_add(1)
+        .ok_or(MarketplaceError::ArithmeticOverflow)?;
+    offer.expires_at = expires_at;
+
+    emit!(OfferCountered {
+        offer: offer.key(),
+        party,
+        amount: new_amount,
+        rounds: offer.rounds,
+        expires_at,
+    });
+
     Ok(())
 }
@@ -0,0 +1,201 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use ticket_nft_core::state::{Event, TicketData};
+
+use crate::state::*;
+use crate::errors::*;
+use crate::payment::*;
+
+#[derive(Accounts)]
+pub struct CreateAuction<'info> {
+    #[account(
+        init,
+        payer = seller,
+        space = Auction::LEN,
+        seeds = [b"auction", ticket_mint.key().as_ref()],
+        bump
+    )]
+    pub auction: Account<'info, Auction>,
+    
+    #[account(mut)]
+    pub seller: Signer<'info>,
+    
+    pub ticket_mint: Account<'info, Mint>,
+    
+    /// Seller's token account holding the ticket
+    #[account(
+        mut,
+        constraint = seller_token_account.mint == ticket_mint.key(),
+        constraint = seller_token_account.owner == seller.key(),
+        constraint = seller_token_account.amount == 1
+    )]
+    pub seller_token_account: Account<'info, TokenAccount>,
+    
+    /// Escrow token account holding the ticket until settlement
+    #[account(
+        init,
+        payer = seller,
+        token::mint = ticket_mint,
+        token::authority = auction,
+        seeds = [b"auction_escrow", auction.key().as_ref()],
+        bump
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+    
+    /// Ticket metadata from ticket-nft-core holding the original face value
+    #[account(
+        seeds = [b"ticket", ticket_mint.key().as_ref()],
+        bump = ticket_data.bump,
+        seeds::program = ticket_nft_core::ID
+    )]
+    pub ticket_data: Account<'info, TicketData>,
+    
+    /// Ticket's event; nothing new is auctioned once it's cancelled
+    #[account(
+        constraint = event.key() == ticket_data.event @ MarketplaceError::EventMismatch,
+        constraint = !event.cancelled @ MarketplaceError::EventCancelled
+    )]
+    pub event: Box<Account<'info, Event>>,
+    
+    /// Royalty configuration for the ticket's event
+    #[account(
+        seeds = [b"royalty_config", royalty_config.event.as_ref()],
+        bump = royalty_config.bump,
+        constraint = royalty_config.event == ticket_data.event @ MarketplaceError::RoyaltyConfigMismatch
+    )]
+    pub royalty_config: Account<'info, RoyaltyConfig>,
+    
+    /// SPL mint the ticket's event is priced in; omitted for SOL
+    pub payment_mint: Option<Account<'info, Mint>>,
+    
+    /// Holds token bids until settlement
+    #[account(
+        init,
+        payer = seller,
+        token::mint = payment_mint,
+        token::authority = auction,
+        seeds = [b"payment_escrow", auction.key().as_ref()],
+        bump
+    )]
+    pub payment_escrow: Option<Account<'info, TokenAccount>>,
+    
+    /// Numbers every auction of the ticket, created with its first one
+    #[account(
+        init_if_needed,
+        payer = seller,
+        space = AuctionCounter::LEN,
+        seeds = [b"auction_counter", ticket_mint.key().as_ref()],
+        bump
+    )]
+    pub auction_counter: Account<'info, AuctionCounter>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Start an auction for a ticket
+pub fn handler(
+    ctx: Context<CreateAuction>,
+    starting_bid: u64,
+    duration_hours: u64,
+    auction_type: AuctionType,
+    reserve_hash: Option<[u8; 32]>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    
+    // Bids are capped at the same anti-scalping limit as fixed-price listings
+    let price_cap = ctx.accounts.ticket_data.face_value
+        .checked_mul(ctx.accounts.royalty_config.price_cap_multiplier as u64)
+        .ok_or(MarketplaceError::ArithmeticOverflow)?
+        .checked_div(10000)
+        .ok_or(MarketplaceError::ArithmeticOverflow)?;
+    require!(starting_bid <= price_cap, MarketplaceError::PriceExceedsCap);
+    require!(
+        duration_hours > 0 && duration_hours <= Auction::MAX_DURATION_HOURS,
+        MarketplaceError::InvalidAuctionConfig
+    );
+    let end_time = (duration_hours as i64)
+        .checked_mul(3600)
+        .and_then(|duration| clock.unix_timestamp.checked_add(duration))
+        .ok_or(MarketplaceError::ArithmeticOverflow)?;
+    
+    // Token auctions collect bids in the payment escrow
+    if payment_mint(ctx.accounts.ticket_data.payment_mint, &ctx.accounts.payment_mint)?.is_some() {
+        require!(ctx.accounts.payment_escrow.is_some(), MarketplaceError::InvalidPaymentAccount);
+    }
+    
+    match &auction_type {
+        AuctionType::English { min_increment, .. } => {
+            if let BidIncrement::BasisPoints(bps) = min_increment {
+                require!(*bps <= 10000, MarketplaceError::InvalidAuctionConfig);
+            }
+        },
+        AuctionType::Dutch { floor_price, decay } => {
+            // The floor is already a public reserve
+            require!(reserve_hash.is_none(), MarketplaceError::InvalidAuctionConfig);
+            require!(*floor_price <= starting_bid, MarketplaceError::InvalidAuctionConfig);
+            if let PriceDecay::Stepwise { interval_seconds, step_amount } = decay {
+                require!(*interval_seconds > 0 && *step_amount > 0, MarketplaceError::InvalidAuctionConfig);
+            }
+        },
+        AuctionType::Sealed { reveal_duration, .. } => {
+            require!(*reveal_duration > 0, MarketplaceError::InvalidAuctionConfig);
+        }
+    }
+    
+    // Move the ticket into auction escrow
+    anchor_spl::token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            anchor_spl::token::Transfer {
+                from: ctx.accounts.seller_token_account.to_account_info(),
+                to: ctx.accounts.escrow_token_account.to_account_info(),
+                authority: ctx.accounts.seller.to_account_info(),
+            },
+        ),
+        1,
+    )?;
+    
+    let auction_counter = &mut ctx.accounts.auction_counter;
+    auction_counter.ticket_mint = ctx.accounts.ticket_mint.key();
+    auction_counter.auctions = auction_counter.auctions
+        .checked_add(1)
+        .ok_or(MarketplaceError::ArithmeticOverflow)?;
+    auction_counter.bump = ctx.bumps.auction_counter;
+    
+    let auction = &mut ctx.accounts.auction;
+    auction.ticket_mint = ctx.accounts.ticket_mint.key();
+    auction.seller = ctx.accounts.seller.key();
+    auction.event = ctx.accounts.ticket_data.event;
+    auction.payment_mint = ctx.accounts.ticket_data.payment_mint;
+    auction.starting_bid = starting_bid;
+    auction.current_bid = starting_bid;
+    auction.highest_bidder = None;
+    auction.second_bid = 0;
+    auction.sealed_bid_count = 0;
+    auction.reserve_hash = reserve_hash;
+    auction.price_cap = price_cap;
+    auction.start_time = clock.unix_timestamp;
+    auction.end_time = end_time;
+    auction.extended_seconds = 0;
+    auction.auction_type = auction_type.clone();
+    auction.status = AuctionStatus::Active;
+    auction.nonce = auction_counter.auctions;
+    auction.bump = ctx.bumps.auction;
+    
+    emit!(AuctionCreated {
+        auction: auction.key(),
+        ticket_mint: auction.ticket_mint,
+        event: auction.event,
+        seller: auction.seller,
+        auction_type,
+        payment_mint: auction.payment_mint,
+        starting_bid,
+        price_cap,
+        end_time: auction.end_time,
+    });
+    
+    Ok(())
+}
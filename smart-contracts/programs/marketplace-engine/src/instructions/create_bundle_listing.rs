@@ -0,0 +1,182 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::{self, get_associated_token_address, AssociatedToken, Create};
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use ticket_nft_core::state::{Event, TicketData};
+
+use crate::state::*;
+use crate::errors::*;
+
+/// Remaining accounts: for each ticket `[ticket_mint, ticket_data, seller_token_account, escrow]`,
+/// escrow being the bundle's associated token account for the mint (created here if it doesn't
+/// exist yet, since anyone can create an associated token account); then for each
+/// distinct event, in order of first appearance, `[event, royalty_config]`
+#[derive(Accounts)]
+#[instruction(bundle_id: u64)]
+pub struct CreateBundleListing<'info> {
+    #[account(
+        init,
+        payer = seller,
+        space = BundleListing::LEN,
+        seeds = [b"bundle", seller.key().as_ref(), &bundle_id.to_le_bytes()],
+        bump
+    )]
+    pub bundle: Account<'info, BundleListing>,
+
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// List `ticket_count` tickets for one price. The cap is the sum of every ticket's own cap
+/// (face value times its event's price cap multiplier).
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CreateBundleListing<'info>>,
+    bundle_id: u64,
+    ticket_count: u8,
+    price: u64,
+    expires_at: Option<i64>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let ticket_count = ticket_count as usize;
+
+    require!(
+        ticket_count > 0 && ticket_count <= BundleListing::MAX_TICKETS,
+        MarketplaceError::InvalidBundle
+    );
+    require!(
+        ctx.remaining_accounts.len() >= ticket_count * 4,
+        MarketplaceError::BundleAccountMismatch
+    );
+    if let Some(expires) = expires_at {
+        require!(expires > clock.unix_timestamp, MarketplaceError::ListingExpired);
+    }
+
+    let (ticket_accounts, event_accounts) = ctx.remaining_accounts.split_at(ticket_count * 4);
+    let bundle_key = ctx.accounts.bundle.key();
+    let mut tickets: Vec<BundleTicket> = Vec::with_capacity(ticket_count);
+    let mut payment_mint = None;
+
+    for (index, accounts) in ticket_accounts.chunks_exact(4).enumerate() {
+        let [ticket_mint, ticket_data, seller_token_account, escrow] = accounts else {
+            return err!(MarketplaceError::BundleAccountMismatch);
+        };
+
+        let ticket_data = Account::<TicketData>::try_from(ticket_data)?;
+        require_keys_eq!(ticket_data.mint, ticket_mint.key(), MarketplaceError::BundleAccountMismatch);
+        require!(
+            tickets.iter().all(|ticket| ticket.ticket_mint != ticket_data.mint),
+            MarketplaceError::InvalidBundle
+        );
+
+        // Every ticket must be priced in the same currency as the first
+        if index == 0 {
+            payment_mint = ticket_data.payment_mint;
+        }
+        require!(ticket_data.payment_mint == payment_mint, MarketplaceError::InvalidBundle);
+
+        let seller_token = Account::<TokenAccount>::try_from(seller_token_account)?;
+        require!(
+            seller_token.mint == ticket_data.mint
+                && seller_token.owner == ctx.accounts.seller.key()
+                && seller_token.amount == 1,
+            MarketplaceError::BundleAccountMismatch
+        );
+        require_keys_eq!(
+            escrow.key(),
+            get_associated_token_address(&bundle_key, &ticket_data.mint),
+            MarketplaceError::BundleAccountMismatch
+        );
+
+        // Move the ticket into the bundle's escrow. Anyone can create the escrow ahead of us, so
+        // an existing one is accepted as long as the bundle owns it and it holds the ticket's mint.
+        associated_token::create_idempotent(CpiContext::new(
+            ctx.accounts.associated_token_program.to_account_info(),
+            Create {
+                payer: ctx.accounts.seller.to_account_info(),
+                associated_token: escrow.clone(),
+                authority: ctx.accounts.bundle.to_account_info(),
+                mint: ticket_mint.clone(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+            },
+        ))?;
+        let escrow_token = Account::<TokenAccount>::try_from(escrow)?;
+        require!(
+            escrow_token.owner == bundle_key && escrow_token.mint == ticket_data.mint,
+            MarketplaceError::BundleAccountMismatch
+        );
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: seller_token_account.clone(),
+                    to: escrow.clone(),
+                    authority: ctx.accounts.seller.to_account_info(),
+                },
+            ),
+            1,
+        )?;
+
+        tickets.push(BundleTicket {
+            ticket_mint: ticket_data.mint,
+            event: ticket_data.event,
+            face_value: ticket_data.face_value,
+        });
+    }
+
+    let bundle = &mut ctx.accounts.bundle;
+    bundle.tickets = tickets;
+
+    // Each event must be live and contributes its tickets' caps
+    let events = bundle.events();
+    require!(
+        event_accounts.len() >= events.len() * 2,
+        MarketplaceError::BundleAccountMismatch
+    );
+    let mut price_cap: u64 = 0;
+    for (event_key, accounts) in events.iter().zip(event_accounts.chunks_exact(2)) {
+        let event = Box::new(Account::<Event>::try_from(&accounts[0])?);
+        require_keys_eq!(event.key(), *event_key, MarketplaceError::EventMismatch);
+        require!(!event.cancelled, MarketplaceError::EventCancelled);
+
+        let royalty_config = Account::<RoyaltyConfig>::try_from(&accounts[1])?;
+        require_keys_eq!(royalty_config.event, *event_key, MarketplaceError::RoyaltyConfigMismatch);
+
+        for ticket in bundle.tickets.iter().filter(|ticket| ticket.event == *event_key) {
+            let ticket_cap = ticket.face_value
+                .checked_mul(royalty_config.price_cap_multiplier as u64)
+                .ok_or(MarketplaceError::ArithmeticOverflow)?
+                / 10000;
+            price_cap = price_cap
+                .checked_add(ticket_cap)
+                .ok_or(MarketplaceError::ArithmeticOverflow)?;
+        }
+    }
+    require!(price <= price_cap, MarketplaceError::PriceExceedsCap);
+
+    bundle.seller = ctx.accounts.seller.key();
+    bundle.bundle_id = bundle_id;
+    bundle.price = price;
+    bundle.payment_mint = payment_mint;
+    bundle.price_cap = price_cap;
+    bundle.expires_at = expires_at;
+    bundle.created_at = clock.unix_timestamp;
+    bundle.status = ListingStatus::Active;
+    bundle.bump = ctx.bumps.bundle;
+
+    emit!(BundleListed {
+        bundle: bundle.key(),
+        seller: bundle.seller,
+        ticket_mints: bundle.tickets.iter().map(|ticket| ticket.ticket_mint).collect(),
+        price,
+        payment_mint,
+        price_cap,
+        expires_at,
+    });
+
+    Ok(())
+}
@@ -1,6 +1,8 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 
+use ticket_nft_core::state::{Event, TicketData};
+
 use crate::state::*;
 use crate::errors::*;
 
@@ -41,13 +43,39 @@ pub struct CreateListing<'info> {
     )]
     pub escrow_token_account: Account<'info, TokenAccount>,
     
+    /// Ticket metadata from ticket-nft-core holding the original face value
+    #[account(
+        seeds = [b"ticket", ticket_mint.key().as_ref()],
+        bump = ticket_data.bump,
+        seeds::program = ticket_nft_core::ID
+    )]
+    pub ticket_data: Account<'info, TicketData>,
+    
+    /// Ticket's event; nothing new is listed once it's cancelled
+    #[account(
+        constraint = event.key() == ticket_data.event @ MarketplaceError::EventMismatch,
+        constraint = !event.cancelled @ MarketplaceError::EventCancelled
+    )]
+    pub event: Box<Account<'info, Event>>,
+    
     /// Royalty configuration for this event
     #[account(
-        seeds = [b"royalty_config", royalty_config.event_mint.as_ref()],
-        bump = royalty_config.bump
+        seeds = [b"royalty_config", royalty_config.event.as_ref()],
+        bump = royalty_config.bump,
+        constraint = royalty_config.event == ticket_data.event @ MarketplaceError::RoyaltyConfigMismatch
     )]
     pub royalty_config: Account<'info, RoyaltyConfig>,
     
+    /// Numbers every listing of the ticket, created with its first one
+    #[account(
+        init_if_needed,
+        payer = seller,
+        space = ListingCounter::LEN,
+        seeds = [b"listing_counter", ticket_mint.key().as_ref()],
+        bump
+    )]
+    pub listing_counter: Account<'info, ListingCounter>,
+    
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
@@ -63,8 +91,8 @@ pub fn handler(
     let royalty_config = &ctx.accounts.royalty_config;
     let clock = Clock::get()?;
     
-    // Calculate price cap based on original ticket price
-    let original_price = 5000000000; // This should come from ticket metadata
+    // Calculate price cap based on the ticket's face value
+    let original_price = ctx.accounts.ticket_data.face_value;
     let price_cap = original_price
         .checked_mul(royalty_config.price_cap_multiplier as u64)
         .ok_or(MarketplaceError::ArithmeticOverflow)?
@@ -89,19 +117,39 @@ pub fn handler(
     let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
     token::transfer(cpi_ctx, 1)?;
     
+    let listing_counter = &mut ctx.accounts.listing_counter;
+    listing_counter.ticket_mint = ctx.accounts.ticket_mint.key();
+    listing_counter.listings = listing_counter.listings
+        .checked_add(1)
+        .ok_or(MarketplaceError::ArithmeticOverflow)?;
+    listing_counter.bump = ctx.bumps.listing_counter;
+    
     // Initialize listing
     listing.ticket_mint = ctx.accounts.ticket_mint.key();
     listing.seller = ctx.accounts.seller.key();
+    listing.event = ctx.accounts.ticket_data.event;
     listing.price = price;
+    listing.payment_mint = ctx.accounts.ticket_data.payment_mint;
     listing.expires_at = expires_at;
     listing.allow_offers = allow_offers;
     listing.created_at = clock.unix_timestamp;
+    listing.nonce = listing_counter.listings;
     listing.status = ListingStatus::Active;
     listing.original_price = original_price;
     listing.price_cap = price_cap;
     listing.bump = ctx.bumps.listing;
     
-    msg!("Listing created: {} SOL", price as f64 / 1_000_000_000.0);
+    emit!(ListingCreated {
+        listing: listing.key(),
+        ticket_mint: listing.ticket_mint,
+        event: listing.event,
+        seller: listing.seller,
+        price,
+        payment_mint: listing.payment_mint,
+        price_cap,
+        expires_at,
+        allow_offers,
+    });
     
     Ok(())
 }
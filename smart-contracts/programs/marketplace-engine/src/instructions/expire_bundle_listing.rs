@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Token;
+
+use crate::state::*;
+use crate::errors::*;
+
+use super::buy_bundle::release_bundle_ticket;
+
+/// Returns every ticket of an expired bundle to its seller and closes the bundle and its escrows.
+/// Anyone may crank this; the cranker earns `BundleListing::EXPIRY_BOUNTY` out of the bundle rent
+/// and the seller gets the rest.
+///
+/// Remaining accounts: for each ticket, in bundle order, `[escrow, seller_token_account]`
+#[derive(Accounts)]
+pub struct ExpireBundleListing<'info> {
+    #[account(
+        mut,
+        seeds = [b"bundle", bundle.seller.as_ref(), &bundle.bundle_id.to_le_bytes()],
+        bump = bundle.bump,
+        close = seller,
+        constraint = bundle.status == ListingStatus::Active @ MarketplaceError::ListingNotActive
+    )]
+    pub bundle: Account<'info, BundleListing>,
+
+    /// Seller gets the tickets and the rent back
+    #[account(
+        mut,
+        constraint = seller.key() == bundle.seller
+    )]
+    pub seller: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, ExpireBundleListing<'info>>) -> Result<()> {
+    let bundle = &ctx.accounts.bundle;
+    let clock = Clock::get()?;
+
+    // Only bundles with an expiry that has passed
+    let expires = bundle.expires_at.ok_or(MarketplaceError::ListingNotExpired)?;
+    require!(clock.unix_timestamp >= expires, MarketplaceError::ListingNotExpired);
+    require!(
+        ctx.remaining_accounts.len() >= bundle.tickets.len() * 2,
+        MarketplaceError::BundleAccountMismatch
+    );
+
+    // Return every ticket to the seller and close the escrows
+    for (ticket, accounts) in bundle.tickets.iter().zip(ctx.remaining_accounts.chunks_exact(2)) {
+        release_bundle_ticket(
+            bundle,
+            ticket,
+            &accounts[0],
+            &accounts[1],
+            bundle.seller,
+            ctx.accounts.seller.to_account_info(),
+            &ctx.accounts.token_program,
+        )?;
+    }
+
+    // Pay the cranker out of the bundle rent; the remainder goes to the seller on close
+    let bundle = &mut ctx.accounts.bundle;
+    let bounty = if ctx.accounts.cranker.key() != bundle.seller {
+        bundle.sub_lamports(BundleListing::EXPIRY_BOUNTY)?;
+        ctx.accounts.cranker.add_lamports(BundleListing::EXPIRY_BOUNTY)?;
+        BundleListing::EXPIRY_BOUNTY
+    } else {
+        0
+    };
+
+    bundle.status = ListingStatus::Expired;
+
+    emit!(BundleExpired {
+        bundle: bundle.key(),
+        seller: bundle.seller,
+        cranker: ctx.accounts.cranker.key(),
+        bounty,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+use crate::state::*;
+use crate::errors::*;
+
+use super::buy_ticket::release_listing_escrow;
+
+/// Returns the ticket of an expired listing to its seller and closes the listing and its escrow.
+/// Anyone may crank this; the cranker earns `Listing::EXPIRY_BOUNTY` out of the listing rent and
+/// the seller gets the rest.
+#[derive(Accounts)]
+pub struct ExpireListing<'info> {
+    #[account(
+        mut,
+        seeds = [b"listing", listing.ticket_mint.as_ref()],
+        bump = listing.bump,
+        close = seller,
+        constraint = listing.status == ListingStatus::Active @ MarketplaceError::ListingNotActive
+    )]
+    pub listing: Account<'info, Listing>,
+    
+    /// Seller gets the ticket and the rent back
+    #[account(
+        mut,
+        constraint = seller.key() == listing.seller
+    )]
+    pub seller: SystemAccount<'info>,
+    
+    /// Seller's token account to receive ticket back
+    #[account(
+        mut,
+        constraint = seller_token_account.mint == listing.ticket_mint,
+        constraint = seller_token_account.owner == listing.seller
+    )]
+    pub seller_token_account: Account<'info, TokenAccount>,
+    
+    /// Escrow token account holding the ticket
+    #[account(
+        mut,
+        seeds = [b"escrow", listing.key().as_ref()],
+        bump,
+        constraint = escrow_token_account.amount == 1
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<ExpireListing>) -> Result<()> {
+    let listing = &mut ctx.accounts.listing;
+    let clock = Clock::get()?;
+    
+    // Only listings with an expiry that has passed
+    let expires = listing.expires_at.ok_or(MarketplaceError::ListingNotExpired)?;
+    require!(clock.unix_timestamp >= expires, MarketplaceError::ListingNotExpired);
+    
+    // Transfer ticket back to seller and close the escrow
+    release_listing_escrow(
+        listing,
+        &ctx.accounts.escrow_token_account,
+        &ctx.accounts.seller_token_account,
+        ctx.accounts.seller.to_account_info(),
+        &ctx.accounts.token_program,
+    )?;
+    
+    // Pay the cranker out of the listing rent; the remainder goes to the seller on close
+    let bounty = if ctx.accounts.cranker.key() != listing.seller {
+        listing.sub_lamports(Listing::EXPIRY_BOUNTY)?;
+        ctx.accounts.cranker.add_lamports(Listing::EXPIRY_BOUNTY)?;
+        Listing::EXPIRY_BOUNTY
+    } else {
+        0
+    };
+    
+    listing.status = ListingStatus::Expired;
+    
+    emit!(ListingExpired {
+        listing: listing.key(),
+        ticket_mint: listing.ticket_mint,
+        seller: listing.seller,
+        cranker: ctx.accounts.cranker.key(),
+        bounty,
+    });
+    
+    Ok(())
+}
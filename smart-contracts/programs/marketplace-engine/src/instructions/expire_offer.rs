@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::state::*;
+use crate::errors::*;
+
+use super::refund_offer::refund_offer_escrow;
+
+/// Permissionless crank that clears an offer once its current round has expired.
+/// Escrow and rent always go back to the buyer.
+#[derive(Accounts)]
+pub struct ExpireOffer<'info> {
+    #[account(
+        mut,
+        seeds = [b"offer", offer.listing.as_ref(), offer.buyer.as_ref(), &offer.listing_nonce.to_le_bytes()],
+        bump = offer.bump,
+        close = buyer,
+        constraint = (offer.status == OfferStatus::Active
+            || offer.status == OfferStatus::CounterOffered) @ MarketplaceError::OfferNotActive
+    )]
+    pub offer: Account<'info, Offer>,
+    
+    /// Buyer who made the offer
+    #[account(
+        mut,
+        constraint = buyer.key() == offer.buyer
+    )]
+    pub buyer: SystemAccount<'info>,
+    
+    /// SPL mint of a token-denominated offer
+    pub payment_mint: Option<Account<'info, Mint>>,
+    
+    /// Token escrow of a token-denominated offer, emptied and closed to the buyer
+    #[account(
+        mut,
+        seeds = [b"payment_escrow", offer.key().as_ref()],
+        bump
+    )]
+    pub payment_escrow: Option<Account<'info, TokenAccount>>,
+    
+    /// Buyer's associated token account receiving the refund
+    #[account(mut)]
+    pub buyer_payment_account: Option<Account<'info, TokenAccount>>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<ExpireOffer>) -> Result<()> {
+    let clock = Clock::get()?;
+    
+    require!(clock.unix_timestamp >= ctx.accounts.offer.expires_at, MarketplaceError::OfferNotExpired);
+    
+    refund_offer_escrow(
+        &ctx.accounts.offer,
+        &ctx.accounts.payment_mint,
+        &ctx.accounts.payment_escrow,
+        &ctx.accounts.buyer.to_account_info(),
+        &ctx.accounts.buyer_payment_account,
+        &ctx.accounts.token_program,
+    )?;
+    
+    let offer = &mut ctx.accounts.offer;
+    offer.status = OfferStatus::Expired;
+    
+    emit!(OfferRefunded {
+        offer: offer.key(),
+        listing: offer.listing,
+        buyer: offer.buyer,
+        amount: offer.amount,
+        status: offer.status.clone(),
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct GetRoyaltyAnalytics<'info> {
+    #[account(
+        seeds = [b"royalty_stats", royalty_stats.event.as_ref()],
+        bump = royalty_stats.bump
+    )]
+    pub royalty_stats: Account<'info, RoyaltyStats>,
+}
+
+/// Get an event's resale totals (volume, sale count, highest and average price, royalties per
+/// payee) as a borsh-encoded `RoyaltyAnalytics` in the return data; simulate to read it
+pub fn handler(ctx: Context<GetRoyaltyAnalytics>) -> Result<()> {
+    let analytics = ctx.accounts.royalty_stats.analytics();
+    set_return_data(&analytics.try_to_vec()?);
+
+    Ok(())
+}
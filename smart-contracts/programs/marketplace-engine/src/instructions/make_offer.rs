@@ -1,6 +1,9 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use ticket_nft_core::state::Event;
 use crate::state::*;
 use crate::errors::*;
+use crate::payment::*;
 
 #[derive(Accounts)]
 pub struct MakeOffer<'info> {
@@ -8,7 +11,7 @@ pub struct MakeOffer<'info> {
         init,
         payer = buyer,
         space = Offer::LEN,
-        seeds = [b"offer", listing.key().as_ref(), buyer.key().as_ref()],
+        seeds = [b"offer", listing.key().as_ref(), buyer.key().as_ref(), &listing.nonce.to_le_bytes()],
         bump
     )]
     pub offer: Account<'info, Offer>,
@@ -22,25 +25,77 @@ pub struct MakeOffer<'info> {
     #[account(mut)]
     pub buyer: Signer<'info>,
     
+    /// Ticket's event; nothing new is offered on once it's cancelled
+    #[account(
+        constraint = event.key() == listing.event @ MarketplaceError::EventMismatch,
+        constraint = !event.cancelled @ MarketplaceError::EventCancelled
+    )]
+    pub event: Box<Account<'info, Event>>,
+    
+    /// SPL mint the listing is priced in; omitted for SOL listings
+    pub payment_mint: Option<Account<'info, Mint>>,
+    
+    /// Buyer's token account funding a token-denominated offer
+    #[account(mut)]
+    pub buyer_payment_account: Option<Account<'info, TokenAccount>>,
+    
+    /// Holds a token-denominated offer until it is accepted or refunded
+    #[account(
+        init,
+        payer = buyer,
+        token::mint = payment_mint,
+        token::authority = offer,
+        seeds = [b"payment_escrow", offer.key().as_ref()],
+        bump
+    )]
+    pub payment_escrow: Option<Account<'info, TokenAccount>>,
+    
+    pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
 
 pub fn handler(ctx: Context<MakeOffer>, amount: u64, expires_at: i64) -> Result<()> {
-    let offer = &mut ctx.accounts.offer;
     let clock = Clock::get()?;
     
     require!(expires_at > clock.unix_timestamp, MarketplaceError::OfferExpired);
+    require!(!ctx.accounts.listing.is_expired(clock.unix_timestamp), MarketplaceError::ListingExpired);
     require!(amount > 0, MarketplaceError::InsufficientFunds);
+    require!(amount <= ctx.accounts.listing.price_cap, MarketplaceError::PriceExceedsCap);
+    
+    // Escrow the offer amount until it is accepted or refunded: lamports in the offer PDA,
+    // tokens in its payment escrow
+    let mint = payment_mint(ctx.accounts.listing.payment_mint, &ctx.accounts.payment_mint)?;
+    let escrow = escrow_account(&ctx.accounts.offer.to_account_info(), &ctx.accounts.payment_escrow, mint)?;
+    wallet_source(
+        &ctx.accounts.buyer,
+        &ctx.accounts.buyer_payment_account,
+        mint,
+        &ctx.accounts.system_program,
+        &ctx.accounts.token_program,
+    )?.pay(&escrow, amount)?;
     
+    let offer = &mut ctx.accounts.offer;
     offer.listing = ctx.accounts.listing.key();
+    offer.listing_nonce = ctx.accounts.listing.nonce;
     offer.buyer = ctx.accounts.buyer.key();
+    offer.event = ctx.accounts.listing.event;
     offer.amount = amount;
+    offer.payment_mint = ctx.accounts.listing.payment_mint;
+    offer.counter_amount = 0;
+    offer.rounds = 0;
     offer.expires_at = expires_at;
     offer.created_at = clock.unix_timestamp;
     offer.status = OfferStatus::Active;
     offer.bump = ctx.bumps.offer;
     
-    msg!("Offer made: {} SOL", amount as f64 / 1_000_000_000.0);
+    emit!(OfferMade {
+        offer: offer.key(),
+        listing: offer.listing,
+        buyer: offer.buyer,
+        amount,
+        payment_mint: offer.payment_mint,
+        expires_at,
+    });
     
     Ok(())
 }
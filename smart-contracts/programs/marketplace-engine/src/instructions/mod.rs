@@ -1 +1,74 @@
-// Module declarations
+// Every module exposes its own `handler`; lib.rs always calls them by full path.
+#![allow(ambiguous_glob_reexports)]
+
+pub mod create_listing;
+pub mod update_listing;
+pub mod cancel_listing;
+pub mod expire_listing;
+pub mod buy_ticket;
+pub mod buy_many;
+pub mod enforce_price_cap;
+pub mod create_bundle_listing;
+pub mod buy_bundle;
+pub mod cancel_bundle_listing;
+pub mod expire_bundle_listing;
+pub mod make_offer;
+pub mod counter_offer;
+pub mod accept_offer;
+pub mod accept_counter_offer;
+pub mod decline_counter_offer;
+pub mod reject_offer;
+pub mod cancel_offer;
+pub mod expire_offer;
+pub mod refund_offer;
+pub mod create_auction;
+pub mod place_bid;
+pub mod claim_bid_refund;
+pub mod buy_dutch;
+pub mod commit_sealed_bid;
+pub mod reveal_sealed_bid;
+pub mod withdraw_sealed_bid;
+pub mod cancel_auction;
+pub mod void_auction;
+pub mod settle_auction;
+pub mod configure_royalty;
+pub mod update_royalty_config;
+pub mod apply_royalty_update;
+pub mod cancel_royalty_update;
+pub mod get_royalty_analytics;
+
+pub use create_listing::*;
+pub use update_listing::*;
+pub use cancel_listing::*;
+pub use expire_listing::*;
+pub use buy_ticket::*;
+pub use buy_many::*;
+pub use enforce_price_cap::*;
+pub use create_bundle_listing::*;
+pub use buy_bundle::*;
+pub use cancel_bundle_listing::*;
+pub use expire_bundle_listing::*;
+pub use make_offer::*;
+pub use counter_offer::*;
+pub use accept_offer::*;
+pub use accept_counter_offer::*;
+pub use decline_counter_offer::*;
+pub use reject_offer::*;
+pub use cancel_offer::*;
+pub use expire_offer::*;
+pub use refund_offer::*;
+pub use create_auction::*;
+pub use place_bid::*;
+pub use claim_bid_refund::*;
+pub use buy_dutch::*;
+pub use commit_sealed_bid::*;
+pub use reveal_sealed_bid::*;
+pub use withdraw_sealed_bid::*;
+pub use cancel_auction::*;
+pub use void_auction::*;
+pub use settle_auction::*;
+pub use configure_royalty::*;
+pub use update_royalty_config::*;
+pub use apply_royalty_update::*;
+pub use cancel_royalty_update::*;
+pub use get_royalty_analytics::*;
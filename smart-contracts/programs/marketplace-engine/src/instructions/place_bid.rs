@@ -0,0 +1,161 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use ticket_nft_core::state::Event;
+
+use crate::state::*;
+use crate::errors::*;
+use crate::payment::*;
+
+#[derive(Accounts)]
+pub struct PlaceBid<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction", auction.ticket_mint.as_ref()],
+        bump = auction.bump
+    )]
+    pub auction: Account<'info, Auction>,
+    
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    
+    /// Ticket's event; nothing new is bid on once it's cancelled
+    #[account(
+        constraint = event.key() == auction.event @ MarketplaceError::EventMismatch,
+        constraint = !event.cancelled @ MarketplaceError::EventCancelled
+    )]
+    pub event: Box<Account<'info, Event>>,
+    
+    /// SPL mint of a token auction
+    pub payment_mint: Option<Account<'info, Mint>>,
+    
+    /// Token escrow holding the current top bid of a token auction
+    #[account(
+        mut,
+        seeds = [b"payment_escrow", auction.key().as_ref()],
+        bump
+    )]
+    pub payment_escrow: Option<Account<'info, TokenAccount>>,
+    
+    /// Bidder's token account funding a token bid
+    #[account(mut)]
+    pub bidder_payment_account: Option<Account<'info, TokenAccount>>,
+    
+    /// Refund owed to the bidder being outbid; required unless the leader is raising their own bid
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        space = BidRefund::LEN,
+        seeds = [
+            b"bid_refund",
+            auction.key().as_ref(),
+            auction.highest_bidder.unwrap_or_default().as_ref(),
+            &auction.nonce.to_le_bytes(),
+        ],
+        bump
+    )]
+    pub previous_bidder_refund: Option<Account<'info, BidRefund>>,
+    
+    /// Token escrow of that refund in a token auction
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        token::mint = payment_mint,
+        token::authority = previous_bidder_refund,
+        seeds = [
+            b"payment_escrow",
+            BidRefund::address(&auction.key(), &auction.highest_bidder.unwrap_or_default(), auction.nonce).as_ref(),
+        ],
+        bump
+    )]
+    pub previous_bidder_refund_escrow: Option<Account<'info, TokenAccount>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Place a bid in an English auction; the bid is escrowed and the previous bid credited to
+/// the outbid bidder's refund account for them to claim
+pub fn handler(ctx: Context<PlaceBid>, bid_amount: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    let bidder_key = ctx.accounts.bidder.key();
+    
+    let (previous_bid, previous_bidder) = {
+        let auction = &ctx.accounts.auction;
+        require!(auction.status == AuctionStatus::Active, MarketplaceError::AuctionNotActive);
+        require!(clock.unix_timestamp < auction.end_time, MarketplaceError::AuctionEnded);
+        require!(bid_amount <= auction.price_cap, MarketplaceError::PriceExceedsCap);
+        
+        // The opening bid may match the starting bid, every later bid must clear the increment
+        require!(bid_amount >= auction.min_next_bid()?, MarketplaceError::BidTooLow);
+        
+        (auction.current_bid, auction.highest_bidder)
+    };
+    
+    let mint = payment_mint(ctx.accounts.auction.payment_mint, &ctx.accounts.payment_mint)?;
+    let auction_info = ctx.accounts.auction.to_account_info();
+    let escrow = escrow_account(&auction_info, &ctx.accounts.payment_escrow, mint)?;
+    
+    // Only the difference needs escrowing when the leader raises their own bid
+    let escrow_amount = match previous_bidder {
+        Some(prev) if prev == bidder_key => bid_amount
+            .checked_sub(previous_bid)
+            .ok_or(MarketplaceError::ArithmeticOverflow)?,
+        Some(prev) => {
+            let ticket_mint = ctx.accounts.auction.ticket_mint;
+            let seeds = &[
+                b"auction",
+                ticket_mint.as_ref(),
+                &[ctx.accounts.auction.bump],
+            ];
+            let signer = &[&seeds[..]];
+            
+            let refund = ctx.accounts.previous_bidder_refund
+                .as_mut()
+                .ok_or(MarketplaceError::PreviousBidderMismatch)?;
+            let refund_to = escrow_account(
+                &refund.to_account_info(),
+                &ctx.accounts.previous_bidder_refund_escrow,
+                mint,
+            )?;
+            escrow_source(
+                &auction_info,
+                &ctx.accounts.payment_escrow,
+                mint,
+                signer,
+                ctx.accounts.token_program.to_account_info(),
+            )?.pay(&refund_to, previous_bid)?;
+            refund.credit(
+                auction_info.key(),
+                &ctx.accounts.auction,
+                prev,
+                bidder_key,
+                ctx.bumps.previous_bidder_refund,
+                previous_bid,
+            )?;
+            bid_amount
+        },
+        None => bid_amount,
+    };
+    
+    wallet_source(
+        &ctx.accounts.bidder,
+        &ctx.accounts.bidder_payment_account,
+        mint,
+        &ctx.accounts.system_program,
+        &ctx.accounts.token_program,
+    )?.pay(&escrow, escrow_amount)?;
+    
+    let auction = &mut ctx.accounts.auction;
+    auction.current_bid = bid_amount;
+    auction.highest_bidder = Some(bidder_key);
+    auction.apply_anti_sniping(clock.unix_timestamp);
+    
+    emit!(BidPlaced {
+        auction: auction.key(),
+        bidder: bidder_key,
+        amount: bid_amount,
+        end_time: auction.end_time,
+    });
+    Ok(())
+}
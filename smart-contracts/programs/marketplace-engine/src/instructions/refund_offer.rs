@@ -0,0 +1,143 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use ticket_nft_core::state::Event;
+
+use crate::state::*;
+use crate::errors::*;
+use crate::payment::*;
+
+/// Returns an escrowed offer once its listing is no longer for sale, including once the listing
+/// account has been closed, or once the ticket's event is cancelled. Anyone may crank this; funds
+/// and rent always go back to the buyer.
+#[derive(Accounts)]
+pub struct RefundOffer<'info> {
+    #[account(
+        mut,
+        seeds = [b"offer", listing.key().as_ref(), offer.buyer.as_ref(), &offer.listing_nonce.to_le_bytes()],
+        bump = offer.bump,
+        close = buyer,
+        constraint = (offer.status == OfferStatus::Active
+            || offer.status == OfferStatus::CounterOffered) @ MarketplaceError::OfferNotActive
+    )]
+    pub offer: Account<'info, Offer>,
+    
+    /// CHECK: Listing the offer was made on; may already be closed, see `listing_active`
+    #[account(address = offer.listing)]
+    pub listing: UncheckedAccount<'info>,
+    
+    /// Ticket's event; pass it once cancelled to refund while the listing is still open
+    #[account(
+        constraint = event.key() == offer.event @ MarketplaceError::EventMismatch
+    )]
+    pub event: Option<Box<Account<'info, Event>>>,
+    
+    /// Buyer who made the offer
+    #[account(
+        mut,
+        constraint = buyer.key() == offer.buyer
+    )]
+    pub buyer: SystemAccount<'info>,
+    
+    /// SPL mint of a token-denominated offer
+    pub payment_mint: Option<Account<'info, Mint>>,
+    
+    /// Token escrow of a token-denominated offer, emptied and closed to the buyer
+    #[account(
+        mut,
+        seeds = [b"payment_escrow", offer.key().as_ref()],
+        bump
+    )]
+    pub payment_escrow: Option<Account<'info, TokenAccount>>,
+    
+    /// Buyer's associated token account receiving the refund
+    #[account(mut)]
+    pub buyer_payment_account: Option<Account<'info, TokenAccount>>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<RefundOffer>) -> Result<()> {
+    let event_cancelled = ctx.accounts.event.as_ref().is_some_and(|event| event.cancelled);
+    require!(
+        event_cancelled || !listing_active(&ctx.accounts.listing, &ctx.accounts.offer)?,
+        MarketplaceError::ListingStillActive
+    );
+    
+    refund_offer_escrow(
+        &ctx.accounts.offer,
+        &ctx.accounts.payment_mint,
+        &ctx.accounts.payment_escrow,
+        &ctx.accounts.buyer.to_account_info(),
+        &ctx.accounts.buyer_payment_account,
+        &ctx.accounts.token_program,
+    )?;
+    
+    let offer = &ctx.accounts.offer;
+    emit!(OfferRefunded {
+        offer: offer.key(),
+        listing: offer.listing,
+        buyer: offer.buyer,
+        amount: offer.amount,
+        status: offer.status.clone(),
+    });
+    
+    Ok(())
+}
+
+/// Whether the listing `offer` was made on is still for sale. Terminal transitions close the
+/// listing account, so an account this program no longer owns is over; a listing with another
+/// nonce is a relisting of the same ticket at the same address and doesn't count either.
+pub fn listing_active(listing: &AccountInfo, offer: &Offer) -> Result<bool> {
+    if listing.owner != &crate::ID || listing.data_is_empty() {
+        return Ok(false);
+    }
+    
+    let listing = Listing::try_deserialize(&mut &listing.data.borrow()[..])?;
+    Ok(listing.status == ListingStatus::Active && listing.nonce == offer.listing_nonce)
+}
+
+/// Return a token-denominated offer's escrow to the buyer and close it. SOL offers need
+/// nothing here: closing the offer account hands the escrowed lamports back.
+pub fn refund_offer_escrow<'info>(
+    offer: &Account<'info, Offer>,
+    payment_mint_account: &Option<Account<'info, Mint>>,
+    payment_escrow: &Option<Account<'info, TokenAccount>>,
+    buyer: &AccountInfo<'info>,
+    buyer_payment_account: &Option<Account<'info, TokenAccount>>,
+    token_program: &Program<'info, Token>,
+) -> Result<()> {
+    let mint = match payment_mint(offer.payment_mint, payment_mint_account)? {
+        Some(mint) => mint,
+        None => return Ok(()),
+    };
+    let escrow = payment_escrow
+        .as_ref()
+        .ok_or(MarketplaceError::InvalidPaymentAccount)?;
+    
+    let listing_nonce = offer.listing_nonce.to_le_bytes();
+    let seeds = &[
+        b"offer",
+        offer.listing.as_ref(),
+        offer.buyer.as_ref(),
+        listing_nonce.as_ref(),
+        &[offer.bump],
+    ];
+    let signer = &[&seeds[..]];
+    
+    PaymentSource::Token {
+        from: escrow.to_account_info(),
+        authority: offer.to_account_info(),
+        signer_seeds: signer,
+        mint,
+        token_program: token_program.to_account_info(),
+    }.pay(&payout_account(buyer, buyer_payment_account, Some(mint))?, escrow.amount)?;
+    
+    close_payment_escrow(
+        payment_escrow,
+        buyer.clone(),
+        offer.to_account_info(),
+        signer,
+        token_program.to_account_info(),
+    )
+}
@@ -0,0 +1,182 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use ticket_nft_core::state::Event;
+
+use crate::state::*;
+use crate::errors::*;
+use crate::payment::*;
+
+#[derive(Accounts)]
+pub struct RevealSealedBid<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction", auction.ticket_mint.as_ref()],
+        bump = auction.bump
+    )]
+    pub auction: Account<'info, Auction>,
+    
+    #[account(
+        mut,
+        seeds = [b"sealed_bid", auction.key().as_ref(), bidder.key().as_ref(), &auction.nonce.to_le_bytes()],
+        bump = sealed_bid.bump
+    )]
+    pub sealed_bid: Account<'info, SealedBid>,
+    
+    pub bidder: Signer<'info>,
+    
+    /// Ticket's event; reveals stop once it's cancelled and deposits can be withdrawn instead
+    #[account(
+        constraint = event.key() == auction.event @ MarketplaceError::EventMismatch,
+        constraint = !event.cancelled @ MarketplaceError::EventCancelled
+    )]
+    pub event: Box<Account<'info, Event>>,
+    
+    /// Sealed bid of the current leader, credited back if this reveal outbids it
+    #[account(mut)]
+    pub previous_leader_bid: Option<Account<'info, SealedBid>>,
+    
+    /// SPL mint of a token auction
+    pub payment_mint: Option<Account<'info, Mint>>,
+    
+    /// Token escrow of the auction, holding the leading bid
+    #[account(
+        mut,
+        seeds = [b"payment_escrow", auction.key().as_ref()],
+        bump
+    )]
+    pub payment_escrow: Option<Account<'info, TokenAccount>>,
+    
+    /// Token escrow of this sealed bid's deposit
+    #[account(
+        mut,
+        seeds = [b"payment_escrow", sealed_bid.key().as_ref()],
+        bump
+    )]
+    pub bid_payment_escrow: Option<Account<'info, TokenAccount>>,
+    
+    /// Token escrow of the previous leader's deposit
+    #[account(mut)]
+    pub previous_leader_payment_escrow: Option<Account<'info, TokenAccount>>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+/// Reveal a sealed bid. A new leader's amount moves into the auction escrow and the
+/// previous leader's amount goes back to their sealed bid account.
+pub fn handler(ctx: Context<RevealSealedBid>, amount: u64, salt: [u8; 32]) -> Result<()> {
+    let clock = Clock::get()?;
+    
+    let (previous_leader, previous_amount) = {
+        let auction = &ctx.accounts.auction;
+        let sealed_bid = &ctx.accounts.sealed_bid;
+        
+        require!(auction.status == AuctionStatus::Active, MarketplaceError::AuctionNotActive);
+        require!(
+            clock.unix_timestamp >= auction.end_time && clock.unix_timestamp < auction.settlement_time(),
+            MarketplaceError::NotInRevealPhase
+        );
+        require!(sealed_bid.is_for(&auction.key(), auction), MarketplaceError::StaleSealedBid);
+        require!(!sealed_bid.revealed, MarketplaceError::BidAlreadyRevealed);
+        require!(
+            SealedBid::commitment(amount, &salt) == sealed_bid.commitment,
+            MarketplaceError::InvalidBidReveal
+        );
+        require!(amount <= sealed_bid.deposit, MarketplaceError::InsufficientFunds);
+        require!(amount >= auction.starting_bid, MarketplaceError::BidTooLow);
+        require!(amount <= auction.price_cap, MarketplaceError::PriceExceedsCap);
+        
+        (auction.highest_bidder, auction.current_bid)
+    };
+    
+    let sealed_bid = &mut ctx.accounts.sealed_bid;
+    sealed_bid.revealed = true;
+    sealed_bid.amount = amount;
+    
+    let mint = payment_mint(ctx.accounts.auction.payment_mint, &ctx.accounts.payment_mint)?;
+    let auction_info = ctx.accounts.auction.to_account_info();
+    let auction_escrow = escrow_account(&auction_info, &ctx.accounts.payment_escrow, mint)?;
+    
+    let leads = previous_leader.is_none() || amount > previous_amount;
+    if leads {
+        if let Some(prev) = previous_leader {
+            let previous_bid = ctx.accounts.previous_leader_bid
+                .as_ref()
+                .ok_or(MarketplaceError::PreviousBidderMismatch)?;
+            require_keys_eq!(previous_bid.bidder, prev, MarketplaceError::PreviousBidderMismatch);
+            require!(
+                previous_bid.is_for(&ctx.accounts.auction.key(), &ctx.accounts.auction),
+                MarketplaceError::PreviousBidderMismatch
+            );
+            
+            // Hand the previous leader's amount back to their own deposit escrow
+            let previous_escrow = match mint {
+                None => previous_bid.to_account_info(),
+                Some(_) => {
+                    let previous_escrow = ctx.accounts.previous_leader_payment_escrow
+                        .as_ref()
+                        .ok_or(MarketplaceError::InvalidPaymentAccount)?;
+                    let (expected, _) = Pubkey::find_program_address(
+                        &[b"payment_escrow", previous_bid.key().as_ref()],
+                        ctx.program_id,
+                    );
+                    require_keys_eq!(previous_escrow.key(), expected, MarketplaceError::InvalidPaymentAccount);
+                    previous_escrow.to_account_info()
+                }
+            };
+            
+            let ticket_mint = ctx.accounts.auction.ticket_mint;
+            let seeds = &[
+                b"auction",
+                ticket_mint.as_ref(),
+                &[ctx.accounts.auction.bump],
+            ];
+            let signer = &[&seeds[..]];
+            escrow_source(
+                &auction_info,
+                &ctx.accounts.payment_escrow,
+                mint,
+                signer,
+                ctx.accounts.token_program.to_account_info(),
+            )?.pay(&previous_escrow, previous_amount)?;
+        }
+        
+        let auction_key = ctx.accounts.auction.key();
+        let bidder_key = ctx.accounts.bidder.key();
+        let auction_nonce = ctx.accounts.auction.nonce.to_le_bytes();
+        let seeds = &[
+            b"sealed_bid",
+            auction_key.as_ref(),
+            bidder_key.as_ref(),
+            auction_nonce.as_ref(),
+            &[ctx.accounts.sealed_bid.bump],
+        ];
+        let signer = &[&seeds[..]];
+        escrow_source(
+            &ctx.accounts.sealed_bid.to_account_info(),
+            &ctx.accounts.bid_payment_escrow,
+            mint,
+            signer,
+            ctx.accounts.token_program.to_account_info(),
+        )?.pay(&auction_escrow, amount)?;
+        
+        let auction = &mut ctx.accounts.auction;
+        if previous_leader.is_some() {
+            auction.second_bid = previous_amount;
+        }
+        auction.current_bid = amount;
+        auction.highest_bidder = Some(ctx.accounts.bidder.key());
+    } else {
+        let auction = &mut ctx.accounts.auction;
+        auction.second_bid = auction.second_bid.max(amount);
+    }
+    
+    emit!(SealedBidRevealed {
+        auction: ctx.accounts.auction.key(),
+        bidder: ctx.accounts.bidder.key(),
+        amount,
+        leading: leads,
+    });
+    
+    Ok(())
+}
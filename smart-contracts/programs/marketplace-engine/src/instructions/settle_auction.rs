@@ -0,0 +1,302 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use ticket_nft_core::state::Event;
+
+use crate::state::*;
+use crate::errors::*;
+use crate::payment::*;
+
+/// Remaining accounts: one per royalty recipient, in RoyaltyConfig order (see `pay_royalties`)
+#[derive(Accounts)]
+pub struct SettleAuction<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction", auction.ticket_mint.as_ref()],
+        bump = auction.bump,
+        close = seller
+    )]
+    pub auction: Account<'info, Auction>,
+    
+    /// Seller receives the proceeds and the reclaimed rent
+    #[account(
+        mut,
+        constraint = seller.key() == auction.seller
+    )]
+    pub seller: SystemAccount<'info>,
+    
+    /// Whoever cranks the settlement; pays the rent of a refund account it creates
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    
+    /// Winner's token account, or the seller's when the ticket isn't sold
+    #[account(
+        mut,
+        constraint = recipient_token_account.mint == auction.ticket_mint
+    )]
+    pub recipient_token_account: Account<'info, TokenAccount>,
+    
+    /// Escrow token account holding the ticket
+    #[account(
+        mut,
+        seeds = [b"auction_escrow", auction.key().as_ref()],
+        bump,
+        constraint = escrow_token_account.amount == 1
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+    
+    /// Auctioned ticket's event; nothing settles once it's cancelled
+    #[account(
+        constraint = event.key() == auction.event @ MarketplaceError::EventMismatch,
+        constraint = !event.cancelled @ MarketplaceError::EventCancelled
+    )]
+    pub event: Box<Account<'info, Event>>,
+    
+    /// Royalty configuration for the auctioned ticket's event
+    #[account(
+        seeds = [b"royalty_config", royalty_config.event.as_ref()],
+        bump = royalty_config.bump,
+        constraint = royalty_config.event == auction.event @ MarketplaceError::RoyaltyConfigMismatch
+    )]
+    pub royalty_config: Account<'info, RoyaltyConfig>,
+    
+    /// Resale totals for the event, updated with this sale
+    #[account(
+        mut,
+        seeds = [b"royalty_stats", auction.event.as_ref()],
+        bump = royalty_stats.bump
+    )]
+    pub royalty_stats: Account<'info, RoyaltyStats>,
+    
+    /// SPL mint of a token auction
+    pub payment_mint: Option<Account<'info, Mint>>,
+    
+    /// Token escrow of a token auction, closed to the seller
+    #[account(
+        mut,
+        seeds = [b"payment_escrow", auction.key().as_ref()],
+        bump
+    )]
+    pub payment_escrow: Option<Account<'info, TokenAccount>>,
+    
+    /// Refund owed to the top bidder, credited when the reserve isn't met or a
+    /// second-price sale clears below their bid
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        space = BidRefund::LEN,
+        seeds = [
+            b"bid_refund",
+            auction.key().as_ref(),
+            auction.highest_bidder.unwrap_or_default().as_ref(),
+            &auction.nonce.to_le_bytes(),
+        ],
+        bump
+    )]
+    pub highest_bidder_refund: Option<Account<'info, BidRefund>>,
+    
+    /// Token escrow of that refund in a token auction
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        token::mint = payment_mint,
+        token::authority = highest_bidder_refund,
+        seeds = [
+            b"payment_escrow",
+            BidRefund::address(&auction.key(), &auction.highest_bidder.unwrap_or_default(), auction.nonce).as_ref(),
+        ],
+        bump
+    )]
+    pub highest_bidder_refund_escrow: Option<Account<'info, TokenAccount>>,
+    
+    /// Seller's associated token account receiving a token sale
+    #[account(mut)]
+    pub seller_payment_account: Option<Account<'info, TokenAccount>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Settle an auction after it ends: ticket to the winner, proceeds split per RoyaltyConfig.
+/// With no bids, or a revealed reserve that wasn't met, the ticket goes back to the seller
+/// and the top bid is credited back to its bidder to claim. Anyone can crank this.
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, SettleAuction<'info>>, reserve_reveal: Option<ReserveReveal>) -> Result<()> {
+    let clock = Clock::get()?;
+    let auction = &ctx.accounts.auction;
+    
+    require!(auction.status == AuctionStatus::Active, MarketplaceError::AuctionNotActive);
+    require!(clock.unix_timestamp >= auction.settlement_time(), MarketplaceError::AuctionStillRunning);
+    
+    // A hidden reserve must be revealed, unless the reveal period lapsed (then the sale stands)
+    let revealed_reserve = match (auction.reserve_hash, reserve_reveal) {
+        (None, _) => None,
+        (Some(hash), Some(reveal)) => {
+            require!(
+                Auction::reserve_commitment(reveal.reserve_price, &reveal.salt) == hash,
+                MarketplaceError::InvalidReserveReveal
+            );
+            Some(reveal.reserve_price)
+        },
+        (Some(_), None) => {
+            require!(
+                clock.unix_timestamp >= auction.settlement_time() + Auction::RESERVE_REVEAL_PERIOD,
+                MarketplaceError::ReserveRevealPending
+            );
+            None
+        },
+    };
+    let reserve_met = match revealed_reserve {
+        Some(reserve_price) => auction.current_bid >= reserve_price,
+        None => true,
+    };
+    
+    let winner = auction.highest_bidder.filter(|_| reserve_met);
+    let recipient = winner.unwrap_or(auction.seller);
+    require_keys_eq!(
+        ctx.accounts.recipient_token_account.owner,
+        recipient,
+        MarketplaceError::Unauthorized
+    );
+    
+    let mint = payment_mint(auction.payment_mint, &ctx.accounts.payment_mint)?;
+    let seeds = &[
+        b"auction",
+        auction.ticket_mint.as_ref(),
+        &[auction.bump],
+    ];
+    let signer = &[&seeds[..]];
+    let source = escrow_source(
+        &auction.to_account_info(),
+        &ctx.accounts.payment_escrow,
+        mint,
+        signer,
+        ctx.accounts.token_program.to_account_info(),
+    )?;
+    
+    let (price, refunded) = if let Some(winner) = winner {
+        let top_bid = auction.current_bid;
+        let final_price = auction.clearing_price(revealed_reserve);
+        let split = ctx.accounts.royalty_config.split(final_price)?;
+        
+        // Pay everyone out of the escrowed winning bid
+        source.pay(
+            &payout_account(&ctx.accounts.seller.to_account_info(), &ctx.accounts.seller_payment_account, mint)?,
+            split.seller_amount,
+        )?;
+        pay_royalties(&source, &split, ctx.remaining_accounts, mint)?;
+        ctx.accounts.royalty_stats.record_sale(final_price, &split)?;
+        
+        // Second-price winners get back what they bid above the clearing price
+        let change = top_bid - final_price;
+        if change > 0 {
+            let refund = ctx.accounts.highest_bidder_refund
+                .as_mut()
+                .ok_or(MarketplaceError::PreviousBidderMismatch)?;
+            source.pay(&escrow_account(&refund.to_account_info(), &ctx.accounts.highest_bidder_refund_escrow, mint)?, change)?;
+            refund.credit(auction.key(), auction, winner, ctx.accounts.cranker.key(), ctx.bumps.highest_bidder_refund, change)?;
+        }
+        
+        emit!(TicketSold {
+            ticket_mint: auction.ticket_mint,
+            event: auction.event,
+            seller: auction.seller,
+            buyer: winner,
+            kind: SaleKind::Auction,
+            price: final_price,
+            payment_mint: auction.payment_mint,
+            royalties: split.royalties,
+            seller_amount: split.seller_amount,
+        });
+        (final_price, change)
+    } else if let Some(top_bidder) = auction.highest_bidder {
+        // Reserve not met: the escrowed top bid is owed back to its bidder
+        let top_bid = auction.current_bid;
+        let refund = ctx.accounts.highest_bidder_refund
+            .as_mut()
+            .ok_or(MarketplaceError::PreviousBidderMismatch)?;
+        source.pay(&escrow_account(&refund.to_account_info(), &ctx.accounts.highest_bidder_refund_escrow, mint)?, top_bid)?;
+        refund.credit(auction.key(), auction, top_bidder, ctx.accounts.cranker.key(), ctx.bumps.highest_bidder_refund, top_bid)?;
+        
+        (0, top_bid)
+    } else {
+        (0, 0)
+    };
+    
+    // Release the ticket and close the escrow token account
+    release_auction_escrow(
+        &ctx.accounts.auction,
+        &ctx.accounts.escrow_token_account,
+        &ctx.accounts.payment_escrow,
+        &ctx.accounts.recipient_token_account,
+        ctx.accounts.seller.to_account_info(),
+        &ctx.accounts.token_program,
+    )?;
+    
+    let auction = &mut ctx.accounts.auction;
+    auction.status = AuctionStatus::Ended;
+    
+    emit!(AuctionSettled {
+        auction: auction.key(),
+        ticket_mint: auction.ticket_mint,
+        seller: auction.seller,
+        winner,
+        price,
+        refunded,
+    });
+    
+    Ok(())
+}
+
+/// Send the escrowed ticket to `recipient` and close the auction's escrow accounts (the ticket
+/// escrow and, for token auctions, the emptied payment escrow), rent to `seller`
+pub fn release_auction_escrow<'info>(
+    auction: &Account<'info, Auction>,
+    escrow_token_account: &Account<'info, TokenAccount>,
+    payment_escrow: &Option<Account<'info, TokenAccount>>,
+    recipient_token_account: &Account<'info, TokenAccount>,
+    seller: AccountInfo<'info>,
+    token_program: &Program<'info, Token>,
+) -> Result<()> {
+    let seeds = &[
+        b"auction",
+        auction.ticket_mint.as_ref(),
+        &[auction.bump],
+    ];
+    let signer = &[&seeds[..]];
+    
+    anchor_spl::token::transfer(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            anchor_spl::token::Transfer {
+                from: escrow_token_account.to_account_info(),
+                to: recipient_token_account.to_account_info(),
+                authority: auction.to_account_info(),
+            },
+            signer,
+        ),
+        1,
+    )?;
+    
+    anchor_spl::token::close_account(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            anchor_spl::token::CloseAccount {
+                account: escrow_token_account.to_account_info(),
+                destination: seller.clone(),
+                authority: auction.to_account_info(),
+            },
+            signer,
+        ),
+    )?;
+    
+    if auction.payment_mint.is_some() {
+        require!(payment_escrow.is_some(), MarketplaceError::InvalidPaymentAccount);
+    }
+    close_payment_escrow(
+        payment_escrow,
+        seller,
+        auction.to_account_info(),
+        signer,
+        token_program.to_account_info(),
+    )
+}
@@ -35,7 +35,6 @@ pub fn handler(
     if let Some(price) = new_price {
         require!(price <= listing.price_cap, MarketplaceError::PriceExceedsCap);
         listing.price = price;
-        msg!("Price updated to: {} SOL", price as f64 / 1_000_000_000.0);
     }
     
     // Update expiration if provided
@@ -49,5 +48,12 @@ pub fn handler(
         listing.allow_offers = offers;
     }
     
+    emit!(ListingUpdated {
+        listing: listing.key(),
+        price: listing.price,
+        expires_at: listing.expires_at,
+        allow_offers: listing.allow_offers,
+    });
+    
     Ok(())
 }
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::errors::*;
+
+/// Queue new royalty terms; they take effect once `RoyaltyConfig::UPDATE_DELAY` has passed
+/// and someone calls `apply_royalty_update`.
+#[derive(Accounts)]
+pub struct UpdateRoyaltyConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"royalty_config", royalty_config.event.as_ref()],
+        bump = royalty_config.bump,
+        has_one = authority @ MarketplaceError::Unauthorized
+    )]
+    pub royalty_config: Account<'info, RoyaltyConfig>,
+    
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<UpdateRoyaltyConfig>, new_terms: RoyaltyTerms) -> Result<()> {
+    let royalty_config = &mut ctx.accounts.royalty_config;
+    let clock = Clock::get()?;
+    
+    require!(royalty_config.pending_update.is_none(), MarketplaceError::RoyaltyUpdatePending);
+    new_terms.validate()?;
+    
+    let effective_at = clock.unix_timestamp
+        .checked_add(RoyaltyConfig::UPDATE_DELAY)
+        .ok_or(MarketplaceError::ArithmeticOverflow)?;
+    royalty_config.pending_update = Some(PendingRoyaltyUpdate {
+        terms: new_terms.clone(),
+        effective_at,
+    });
+    
+    emit!(RoyaltyUpdateQueued {
+        royalty_config: royalty_config.key(),
+        old_terms: royalty_config.terms(),
+        new_terms,
+        effective_at,
+    });
+    
+    Ok(())
+}
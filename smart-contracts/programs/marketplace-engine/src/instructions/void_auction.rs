@@ -0,0 +1,157 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use ticket_nft_core::state::Event;
+
+use crate::state::*;
+use crate::errors::*;
+use crate::payment::*;
+
+use super::settle_auction::release_auction_escrow;
+
+#[derive(Accounts)]
+pub struct VoidAuction<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction", auction.ticket_mint.as_ref()],
+        bump = auction.bump,
+        close = seller
+    )]
+    pub auction: Account<'info, Auction>,
+    
+    /// Auctioned ticket's event, which must have been cancelled
+    #[account(
+        constraint = event.key() == auction.event @ MarketplaceError::EventMismatch,
+        constraint = event.cancelled @ MarketplaceError::EventNotCancelled
+    )]
+    pub event: Box<Account<'info, Event>>,
+    
+    /// Whoever cranks the void; pays the rent of the top bidder's refund account
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    
+    /// Seller gets the ticket back and the reclaimed rent
+    #[account(
+        mut,
+        constraint = seller.key() == auction.seller
+    )]
+    pub seller: SystemAccount<'info>,
+    
+    /// Seller's token account to receive ticket back
+    #[account(
+        mut,
+        constraint = seller_token_account.mint == auction.ticket_mint,
+        constraint = seller_token_account.owner == auction.seller
+    )]
+    pub seller_token_account: Account<'info, TokenAccount>,
+    
+    /// Escrow token account holding the ticket
+    #[account(
+        mut,
+        seeds = [b"auction_escrow", auction.key().as_ref()],
+        bump,
+        constraint = escrow_token_account.amount == 1
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+    
+    /// SPL mint of a token auction
+    pub payment_mint: Option<Account<'info, Mint>>,
+    
+    /// Token escrow of a token auction, closed to the seller
+    #[account(
+        mut,
+        seeds = [b"payment_escrow", auction.key().as_ref()],
+        bump
+    )]
+    pub payment_escrow: Option<Account<'info, TokenAccount>>,
+    
+    /// Refund owed to the top bidder, credited when the auction has a bid
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        space = BidRefund::LEN,
+        seeds = [
+            b"bid_refund",
+            auction.key().as_ref(),
+            auction.highest_bidder.unwrap_or_default().as_ref(),
+            &auction.nonce.to_le_bytes(),
+        ],
+        bump
+    )]
+    pub highest_bidder_refund: Option<Account<'info, BidRefund>>,
+    
+    /// Token escrow of that refund in a token auction
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        token::mint = payment_mint,
+        token::authority = highest_bidder_refund,
+        seeds = [
+            b"payment_escrow",
+            BidRefund::address(&auction.key(), &auction.highest_bidder.unwrap_or_default(), auction.nonce).as_ref(),
+        ],
+        bump
+    )]
+    pub highest_bidder_refund_escrow: Option<Account<'info, TokenAccount>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Unwind an auction whose event was cancelled: the escrowed top bid is credited back to its
+/// bidder to claim and the ticket goes back to the seller. Anyone can crank this; unrevealed
+/// sealed bids are reclaimed through `withdraw_sealed_bid`.
+pub fn handler(ctx: Context<VoidAuction>) -> Result<()> {
+    let auction = &ctx.accounts.auction;
+    
+    require!(auction.status == AuctionStatus::Active, MarketplaceError::AuctionNotActive);
+    
+    let refunded = if let Some(top_bidder) = auction.highest_bidder {
+        let mint = payment_mint(auction.payment_mint, &ctx.accounts.payment_mint)?;
+        let seeds = &[
+            b"auction",
+            auction.ticket_mint.as_ref(),
+            &[auction.bump],
+        ];
+        let signer = &[&seeds[..]];
+        
+        let refund = ctx.accounts.highest_bidder_refund
+            .as_mut()
+            .ok_or(MarketplaceError::PreviousBidderMismatch)?;
+        escrow_source(
+            &auction.to_account_info(),
+            &ctx.accounts.payment_escrow,
+            mint,
+            signer,
+            ctx.accounts.token_program.to_account_info(),
+        )?.pay(
+            &escrow_account(&refund.to_account_info(), &ctx.accounts.highest_bidder_refund_escrow, mint)?,
+            auction.current_bid,
+        )?;
+        refund.credit(auction.key(), auction, top_bidder, ctx.accounts.cranker.key(), ctx.bumps.highest_bidder_refund, auction.current_bid)?;
+        auction.current_bid
+    } else {
+        0
+    };
+    
+    release_auction_escrow(
+        &ctx.accounts.auction,
+        &ctx.accounts.escrow_token_account,
+        &ctx.accounts.payment_escrow,
+        &ctx.accounts.seller_token_account,
+        ctx.accounts.seller.to_account_info(),
+        &ctx.accounts.token_program,
+    )?;
+    
+    let auction = &mut ctx.accounts.auction;
+    auction.status = AuctionStatus::Cancelled;
+    
+    emit!(AuctionCancelled {
+        auction: auction.key(),
+        ticket_mint: auction.ticket_mint,
+        seller: auction.seller,
+        refunded_bidder: auction.highest_bidder,
+        refunded,
+    });
+    Ok(())
+}
@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use ticket_nft_core::state::Event;
+
+use crate::state::*;
+use crate::errors::*;
+use crate::payment::*;
+
+#[derive(Accounts)]
+pub struct WithdrawSealedBid<'info> {
+    #[account(
+        mut,
+        seeds = [b"sealed_bid", sealed_bid.auction.as_ref(), bidder.key().as_ref(), &sealed_bid.auction_nonce.to_le_bytes()],
+        bump = sealed_bid.bump,
+        close = bidder
+    )]
+    pub sealed_bid: Account<'info, SealedBid>,
+    
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    
+    /// Auction's event; pass it once cancelled to withdraw without waiting out the reveal phase
+    #[account(
+        constraint = event.key() == sealed_bid.event @ MarketplaceError::EventMismatch
+    )]
+    pub event: Option<Box<Account<'info, Event>>>,
+    
+    /// SPL mint of a token auction
+    pub payment_mint: Option<Account<'info, Mint>>,
+    
+    /// Token escrow of this sealed bid's deposit, emptied and closed to the bidder
+    #[account(
+        mut,
+        seeds = [b"payment_escrow", sealed_bid.key().as_ref()],
+        bump
+    )]
+    pub payment_escrow: Option<Account<'info, TokenAccount>>,
+    
+    /// Bidder's associated token account receiving the deposit
+    #[account(mut)]
+    pub bidder_payment_account: Option<Account<'info, TokenAccount>>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+/// Reclaim a sealed bid deposit (minus any winning amount) once the reveal phase is over,
+/// or straight away if the event was cancelled
+pub fn handler(ctx: Context<WithdrawSealedBid>) -> Result<()> {
+    let clock = Clock::get()?;
+    let sealed_bid = &ctx.accounts.sealed_bid;
+    
+    let event_cancelled = ctx.accounts.event.as_ref().is_some_and(|event| event.cancelled);
+    require!(
+        event_cancelled || clock.unix_timestamp >= sealed_bid.reveal_end,
+        MarketplaceError::RevealPhaseActive
+    );
+    
+    // SOL deposits come back when the sealed bid account closes; tokens are swept here
+    let reclaimed = if let Some(mint) = payment_mint(sealed_bid.payment_mint, &ctx.accounts.payment_mint)? {
+        let escrow = ctx.accounts.payment_escrow
+            .as_ref()
+            .ok_or(MarketplaceError::InvalidPaymentAccount)?;
+        let auction_nonce = sealed_bid.auction_nonce.to_le_bytes();
+        let seeds = &[
+            b"sealed_bid",
+            sealed_bid.auction.as_ref(),
+            sealed_bid.bidder.as_ref(),
+            auction_nonce.as_ref(),
+            &[sealed_bid.bump],
+        ];
+        let signer = &[&seeds[..]];
+        
+        let bidder = ctx.accounts.bidder.to_account_info();
+        PaymentSource::Token {
+            from: escrow.to_account_info(),
+            authority: sealed_bid.to_account_info(),
+            signer_seeds: signer,
+            mint,
+            token_program: ctx.accounts.token_program.to_account_info(),
+        }.pay(&payout_account(&bidder, &ctx.accounts.bidder_payment_account, Some(mint))?, escrow.amount)?;
+        
+        close_payment_escrow(
+            &ctx.accounts.payment_escrow,
+            bidder,
+            sealed_bid.to_account_info(),
+            signer,
+            ctx.accounts.token_program.to_account_info(),
+        )?;
+        escrow.amount
+    } else {
+        let rent = Rent::get()?.minimum_balance(SealedBid::LEN);
+        sealed_bid.to_account_info().lamports().saturating_sub(rent)
+    };
+    
+    emit!(SealedBidWithdrawn {
+        auction: sealed_bid.auction,
+        bidder: sealed_bid.bidder,
+        reclaimed,
+        event_cancelled,
+    });
+    
+    Ok(())
+}
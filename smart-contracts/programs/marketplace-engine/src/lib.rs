@@ -1,336 +1,187 @@
 use anchor_lang::prelude::*;
 
-declare_id!("4MangoMjqJ2firMokCjjGgoTQjRNMjLi1KN1dj7iGKvK");
-
+pub mod instructions;
 pub mod state;
 pub mod errors;
+pub mod payment;
 
+use instructions::*;
 use state::*;
-use errors::*;
+
+declare_id!("4MangoMjqJ2firMokCjjGgoTQjRNMjLi1KN1dj7iGKvK");
 
 #[program]
 pub mod marketplace_engine {
     use super::*;
 
+    // Fixed-price listings (ticket held in escrow until sold or cancelled)
     pub fn create_listing(
         ctx: Context<CreateListing>,
         price: u64,
-        _expires_at: Option<i64>,
-        _allow_offers: bool,
+        expires_at: Option<i64>,
+        allow_offers: bool,
+    ) -> Result<()> {
+        instructions::create_listing::handler(ctx, price, expires_at, allow_offers)
+    }
+
+    pub fn update_listing(
+        ctx: Context<UpdateListing>,
+        new_price: Option<u64>,
+        expires_at: Option<i64>,
+        allow_offers: Option<bool>,
+    ) -> Result<()> {
+        instructions::update_listing::handler(ctx, new_price, expires_at, allow_offers)
+    }
+
+    pub fn cancel_listing(ctx: Context<CancelListing>) -> Result<()> {
+        instructions::cancel_listing::handler(ctx)
+    }
+
+    pub fn expire_listing(ctx: Context<ExpireListing>) -> Result<()> {
+        instructions::expire_listing::handler(ctx)
+    }
+
+    pub fn buy_ticket<'info>(ctx: Context<'_, '_, '_, 'info, BuyTicket<'info>>) -> Result<()> {
+        instructions::buy_ticket::handler(ctx)
+    }
+
+    pub fn buy_many<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BuyMany<'info>>,
+        listing_count: u8,
+        allow_partial: bool,
+    ) -> Result<()> {
+        instructions::buy_many::handler(ctx, listing_count, allow_partial)
+    }
+
+    pub fn enforce_price_cap(ctx: Context<EnforcePriceCap>) -> Result<()> {
+        instructions::enforce_price_cap::handler(ctx)
+    }
+
+    // Bundle listings (several tickets escrowed together and sold for one price)
+    pub fn create_bundle_listing<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreateBundleListing<'info>>,
+        bundle_id: u64,
+        ticket_count: u8,
+        price: u64,
+        expires_at: Option<i64>,
     ) -> Result<()> {
-        let listing = &mut ctx.accounts.listing;
-        
-        listing.ticket_mint = ctx.accounts.ticket_mint.key();
-        listing.seller = ctx.accounts.seller.key();
-        listing.price = price;
-        listing.original_price = 5_000_000_000; // 5 SOL original price
-        listing.price_cap = listing.original_price * 2; // 200% price cap
-        listing.status = ListingStatus::Active;
-        listing.bump = ctx.bumps.listing;
-        
-        msg!("🎫 Listing created for {} SOL", price as f64 / 1_000_000_000.0);
-        Ok(())
-    }
-
-    pub fn buy_ticket(ctx: Context<BuyTicket>) -> Result<()> {
-        let listing = &mut ctx.accounts.listing;
-        let royalty_config = &ctx.accounts.royalty_config;
-        
-        // Validate listing is active
-        require!(listing.status == ListingStatus::Active, MarketplaceError::ListingNotActive);
-        
-        let total_price = listing.price;
-        
-        // Calculate royalty distributions (using basis points: 1000 = 10%)
-        let artist_royalty = total_price
-            .checked_mul(royalty_config.artist_percentage as u64)
-            .ok_or(MarketplaceError::ArithmeticOverflow)?
-            .checked_div(10000)
-            .ok_or(MarketplaceError::ArithmeticOverflow)?;
-        
-        let venue_royalty = total_price
-            .checked_mul(royalty_config.venue_percentage as u64)
-            .ok_or(MarketplaceError::ArithmeticOverflow)?
-            .checked_div(10000)
-            .ok_or(MarketplaceError::ArithmeticOverflow)?;
-        
-        let platform_fee = total_price
-            .checked_mul(royalty_config.platform_percentage as u64)
-            .ok_or(MarketplaceError::ArithmeticOverflow)?
-            .checked_div(10000)
-            .ok_or(MarketplaceError::ArithmeticOverflow)?;
-        
-        let seller_amount = total_price
-            .checked_sub(artist_royalty)
-            .ok_or(MarketplaceError::ArithmeticOverflow)?
-            .checked_sub(venue_royalty)
-            .ok_or(MarketplaceError::ArithmeticOverflow)?
-            .checked_sub(platform_fee)
-            .ok_or(MarketplaceError::ArithmeticOverflow)?;
-        
-        // Pay everyone instantly
-        // Pay seller
-        anchor_lang::system_program::transfer(
-            CpiContext::new(
-                ctx.accounts.system_program.to_account_info(),
-                anchor_lang::system_program::Transfer {
-                    from: ctx.accounts.buyer.to_account_info(),
-                    to: ctx.accounts.seller.to_account_info(),
-                },
-            ),
-            seller_amount,
-        )?;
-        
-        // Pay artist royalty (REVOLUTIONARY!)
-        if artist_royalty > 0 {
-            anchor_lang::system_program::transfer(
-                CpiContext::new(
-                    ctx.accounts.system_program.to_account_info(),
-                    anchor_lang::system_program::Transfer {
-                        from: ctx.accounts.buyer.to_account_info(),
-                        to: ctx.accounts.artist_wallet.to_account_info(),
-                    },
-                ),
-                artist_royalty,
-            )?;
-        }
-        
-        // Pay venue royalty
-        if venue_royalty > 0 {
-            anchor_lang::system_program::transfer(
-                CpiContext::new(
-                    ctx.accounts.system_program.to_account_info(),
-                    anchor_lang::system_program::Transfer {
-                        from: ctx.accounts.buyer.to_account_info(),
-                        to: ctx.accounts.venue_wallet.to_account_info(),
-                    },
-                ),
-                venue_royalty,
-            )?;
-        }
-        
-        // Mark as sold
-        listing.status = ListingStatus::Sold;
-        
-        msg!("🎉 REVOLUTIONARY SALE! Artist: {} SOL, Venue: {} SOL, Seller: {} SOL", 
-             artist_royalty as f64 / 1_000_000_000.0,
-             venue_royalty as f64 / 1_000_000_000.0,
-             seller_amount as f64 / 1_000_000_000.0);
-        
-        Ok(())
-    }
-
-    /// Start an auction for a ticket
+        instructions::create_bundle_listing::handler(ctx, bundle_id, ticket_count, price, expires_at)
+    }
+
+    pub fn buy_bundle<'info>(ctx: Context<'_, '_, 'info, 'info, BuyBundle<'info>>) -> Result<()> {
+        instructions::buy_bundle::handler(ctx)
+    }
+
+    pub fn cancel_bundle_listing<'info>(ctx: Context<'_, '_, 'info, 'info, CancelBundleListing<'info>>) -> Result<()> {
+        instructions::cancel_bundle_listing::handler(ctx)
+    }
+
+    pub fn expire_bundle_listing<'info>(ctx: Context<'_, '_, 'info, 'info, ExpireBundleListing<'info>>) -> Result<()> {
+        instructions::expire_bundle_listing::handler(ctx)
+    }
+
+    // Offers and counter-offers (offer amount held in escrow)
+    pub fn make_offer(ctx: Context<MakeOffer>, amount: u64, expires_at: i64) -> Result<()> {
+        instructions::make_offer::handler(ctx, amount, expires_at)
+    }
+
+    pub fn counter_offer(ctx: Context<CounterOffer>, new_amount: u64, expires_at: i64) -> Result<()> {
+        instructions::counter_offer::handler(ctx, new_amount, expires_at)
+    }
+
+    pub fn accept_offer<'info>(ctx: Context<'_, '_, '_, 'info, AcceptOffer<'info>>) -> Result<()> {
+        instructions::accept_offer::handler(ctx)
+    }
+
+    pub fn accept_counter_offer<'info>(ctx: Context<'_, '_, '_, 'info, AcceptCounterOffer<'info>>) -> Result<()> {
+        instructions::accept_counter_offer::handler(ctx)
+    }
+
+    pub fn decline_counter_offer(ctx: Context<DeclineCounterOffer>) -> Result<()> {
+        instructions::decline_counter_offer::handler(ctx)
+    }
+
+    pub fn reject_offer(ctx: Context<RejectOffer>) -> Result<()> {
+        instructions::reject_offer::handler(ctx)
+    }
+
+    pub fn cancel_offer(ctx: Context<CancelOffer>) -> Result<()> {
+        instructions::cancel_offer::handler(ctx)
+    }
+
+    pub fn expire_offer(ctx: Context<ExpireOffer>) -> Result<()> {
+        instructions::expire_offer::handler(ctx)
+    }
+
+    pub fn refund_offer(ctx: Context<RefundOffer>) -> Result<()> {
+        instructions::refund_offer::handler(ctx)
+    }
+
+    // Auctions (English, Dutch and sealed-bid)
     pub fn create_auction(
         ctx: Context<CreateAuction>,
         starting_bid: u64,
         duration_hours: u64,
         auction_type: AuctionType,
+        reserve_hash: Option<[u8; 32]>,
     ) -> Result<()> {
-        let auction = &mut ctx.accounts.auction;
-        let clock = Clock::get()?;
-        
-        auction.ticket_mint = ctx.accounts.ticket_mint.key();
-        auction.seller = ctx.accounts.seller.key();
-        auction.starting_bid = starting_bid;
-        auction.current_bid = starting_bid;
-        auction.highest_bidder = None;
-        auction.end_time = clock.unix_timestamp + (duration_hours as i64 * 3600);
-        auction.auction_type = auction_type.clone();
-        auction.status = AuctionStatus::Active;
-        auction.bump = ctx.bumps.auction;
-        
-        match auction_type {
-            AuctionType::English => {
-                msg!("🔥 English auction started! Starting bid: {} SOL", starting_bid as f64 / 1_000_000_000.0);
-            },
-            AuctionType::Dutch => {
-                msg!("⚡ Dutch auction started! Price drops from {} SOL", starting_bid as f64 / 1_000_000_000.0);
-            }
-        }
-        
-        Ok(())
-    }
-
-    /// Place a bid (simplified version that compiles)
+        instructions::create_auction::handler(ctx, starting_bid, duration_hours, auction_type, reserve_hash)
+    }
+
     pub fn place_bid(ctx: Context<PlaceBid>, bid_amount: u64) -> Result<()> {
-        let auction = &mut ctx.accounts.auction;
-        let clock = Clock::get()?;
-        
-        require!(auction.status == AuctionStatus::Active, MarketplaceError::ListingNotActive);
-        require!(clock.unix_timestamp < auction.end_time, MarketplaceError::ListingNotActive);
-        require!(bid_amount > auction.current_bid, MarketplaceError::InsufficientFunds);
-        
-        auction.current_bid = bid_amount;
-        auction.highest_bidder = Some(ctx.accounts.bidder.key());
-        
-        msg!("🚀 New highest bid: {} SOL", bid_amount as f64 / 1_000_000_000.0);
-        Ok(())
+        instructions::place_bid::handler(ctx, bid_amount)
     }
-}
 
-#[derive(Accounts)]
-pub struct CreateListing<'info> {
-    #[account(
-        init,
-        payer = seller,
-        space = Listing::LEN,
-        seeds = [b"listing", ticket_mint.key().as_ref()],
-        bump
-    )]
-    pub listing: Account<'info, Listing>,
-    
-    #[account(mut)]
-    pub seller: Signer<'info>,
-    
-    pub ticket_mint: AccountInfo<'info>,
-    
-    pub system_program: Program<'info, System>,
-}
+    pub fn claim_bid_refund(ctx: Context<ClaimBidRefund>) -> Result<()> {
+        instructions::claim_bid_refund::handler(ctx)
+    }
 
-#[derive(Accounts)]
-pub struct BuyTicket<'info> {
-    #[account(
-        mut,
-        seeds = [b"listing", listing.ticket_mint.as_ref()],
-        bump = listing.bump
-    )]
-    pub listing: Account<'info, Listing>,
-    
-    #[account(mut)]
-    pub buyer: Signer<'info>,
-    
-    #[account(
-        mut,
-        constraint = seller.key() == listing.seller
-    )]
-    pub seller: SystemAccount<'info>,
-    
-    pub royalty_config: Account<'info, RoyaltyConfig>,
-    
-    #[account(
-        mut,
-        constraint = artist_wallet.key() == royalty_config.artist_wallet
-    )]
-    pub artist_wallet: SystemAccount<'info>,
-    
-    #[account(
-        mut,
-        constraint = venue_wallet.key() == royalty_config.venue_wallet
-    )]
-    pub venue_wallet: SystemAccount<'info>,
-    
-    pub system_program: Program<'info, System>,
-}
+    pub fn buy_dutch<'info>(ctx: Context<'_, '_, '_, 'info, BuyDutch<'info>>) -> Result<()> {
+        instructions::buy_dutch::handler(ctx)
+    }
 
-#[derive(Accounts)]
-pub struct CreateAuction<'info> {
-    #[account(
-        init,
-        payer = seller,
-        space = Auction::LEN,
-        seeds = [b"auction", ticket_mint.key().as_ref()],
-        bump
-    )]
-    pub auction: Account<'info, Auction>,
-    
-    #[account(mut)]
-    pub seller: Signer<'info>,
-    
-    pub ticket_mint: AccountInfo<'info>,
-    
-    pub system_program: Program<'info, System>,
-}
+    pub fn commit_sealed_bid(ctx: Context<CommitSealedBid>, commitment: [u8; 32], deposit: u64) -> Result<()> {
+        instructions::commit_sealed_bid::handler(ctx, commitment, deposit)
+    }
 
-#[derive(Accounts)]
-pub struct PlaceBid<'info> {
-    #[account(
-        mut,
-        seeds = [b"auction", auction.ticket_mint.as_ref()],
-        bump = auction.bump
-    )]
-    pub auction: Account<'info, Auction>,
-    
-    #[account(mut)]
-    pub bidder: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
-}
+    pub fn reveal_sealed_bid(ctx: Context<RevealSealedBid>, amount: u64, salt: [u8; 32]) -> Result<()> {
+        instructions::reveal_sealed_bid::handler(ctx, amount, salt)
+    }
 
-    /// Configure royalty percentages for an event
-    pub fn configure_royalty(
-        ctx: Context<ConfigureRoyalty>,
-        artist_percentage: u16,   // 1000 = 10%
-        venue_percentage: u16,    // 500 = 5%
-        platform_percentage: u16, // 100 = 1%
-        price_cap_multiplier: u16, // 20000 = 200%
-    ) -> Result<()> {
-        let royalty_config = &mut ctx.accounts.royalty_config;
-        
-        // Validate percentages don't exceed 100%
-        let total_percentage = artist_percentage + venue_percentage + platform_percentage;
-        require!(total_percentage <= 10000, MarketplaceError::ArithmeticOverflow);
-        
-        royalty_config.event_mint = ctx.accounts.event_mint.key();
-        royalty_config.artist_wallet = ctx.accounts.artist_wallet.key();
-        royalty_config.venue_wallet = ctx.accounts.venue_wallet.key();
-        royalty_config.platform_wallet = ctx.accounts.platform_wallet.key();
-        royalty_config.artist_percentage = artist_percentage;
-        royalty_config.venue_percentage = venue_percentage;
-        royalty_config.platform_percentage = platform_percentage;
-        royalty_config.price_cap_multiplier = price_cap_multiplier;
-        royalty_config.bump = ctx.bumps.royalty_config;
-        
-        msg!("💰 Royalty config set! Artist: {}%, Venue: {}%, Price cap: {}%",
-             artist_percentage as f64 / 100.0,
-             venue_percentage as f64 / 100.0,
-             price_cap_multiplier as f64 / 100.0);
-        
-        Ok(())
-    }
-
-    /// Get royalty analytics (how much earned)
-    pub fn get_royalty_analytics(ctx: Context<GetRoyaltyAnalytics>) -> Result<()> {
-        let royalty_config = &ctx.accounts.royalty_config;
-        
-        // This would typically query historical transactions
-        // For now, we'll just show the configuration
-        msg!("📊 ROYALTY ANALYTICS:");
-        msg!("Artist wallet: {}", royalty_config.artist_wallet);
-        msg!("Artist percentage: {}%", royalty_config.artist_percentage as f64 / 100.0);
-        msg!("Venue percentage: {}%", royalty_config.venue_percentage as f64 / 100.0);
-        msg!("Price cap: {}%", royalty_config.price_cap_multiplier as f64 / 100.0);
-        
-        Ok(())
-    }
-
-#[derive(Accounts)]
-pub struct ConfigureRoyalty<'info> {
-    #[account(
-        init,
-        payer = authority,
-        space = RoyaltyConfig::LEN,
-        seeds = [b"royalty_config", event_mint.key().as_ref()],
-        bump
-    )]
-    pub royalty_config: Account<'info, RoyaltyConfig>,
-    
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    
-    pub event_mint: AccountInfo<'info>,
-    pub artist_wallet: AccountInfo<'info>,
-    pub venue_wallet: AccountInfo<'info>,
-    pub platform_wallet: AccountInfo<'info>,
-    
-    pub system_program: Program<'info, System>,
-}
+    pub fn withdraw_sealed_bid(ctx: Context<WithdrawSealedBid>) -> Result<()> {
+        instructions::withdraw_sealed_bid::handler(ctx)
+    }
+
+    pub fn cancel_auction(ctx: Context<CancelAuction>) -> Result<()> {
+        instructions::cancel_auction::handler(ctx)
+    }
 
-#[derive(Accounts)]
-pub struct GetRoyaltyAnalytics<'info> {
-    #[account(
-        seeds = [b"royalty_config", royalty_config.event_mint.as_ref()],
-        bump = royalty_config.bump
-    )]
-    pub royalty_config: Account<'info, RoyaltyConfig>,
+    pub fn void_auction(ctx: Context<VoidAuction>) -> Result<()> {
+        instructions::void_auction::handler(ctx)
+    }
+
+    pub fn settle_auction<'info>(ctx: Context<'_, '_, '_, 'info, SettleAuction<'info>>, reserve_reveal: Option<ReserveReveal>) -> Result<()> {
+        instructions::settle_auction::handler(ctx, reserve_reveal)
+    }
+
+    // Royalties
+    pub fn configure_royalty(ctx: Context<ConfigureRoyalty>, terms: RoyaltyTerms) -> Result<()> {
+        instructions::configure_royalty::handler(ctx, terms)
+    }
+
+    pub fn update_royalty_config(ctx: Context<UpdateRoyaltyConfig>, new_terms: RoyaltyTerms) -> Result<()> {
+        instructions::update_royalty_config::handler(ctx, new_terms)
+    }
+
+    pub fn apply_royalty_update(ctx: Context<ApplyRoyaltyUpdate>) -> Result<()> {
+        instructions::apply_royalty_update::handler(ctx)
+    }
+
+    pub fn cancel_royalty_update(ctx: Context<CancelRoyaltyUpdate>) -> Result<()> {
+        instructions::cancel_royalty_update::handler(ctx)
+    }
+
+    pub fn get_royalty_analytics(ctx: Context<GetRoyaltyAnalytics>) -> Result<()> {
+        instructions::get_royalty_analytics::handler(ctx)
+    }
 }
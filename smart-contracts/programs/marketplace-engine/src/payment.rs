@@ -0,0 +1,235 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::{self, CloseAccount, Mint, Token, TokenAccount, TransferChecked};
+
+use crate::errors::*;
+use crate::state::RoyaltySplit;
+
+/// Where settlement funds are drawn from.
+///
+/// Listings and auctions priced in SOL pay from a signing wallet or from lamports escrowed in a
+/// program PDA. Those priced in an SPL `payment_mint` move tokens with `transfer_checked`, either
+/// from the buyer's token account or from a `[b"payment_escrow", owner]` token account.
+pub enum PaymentSource<'a, 'info> {
+    /// A signer paying SOL as part of the instruction
+    Wallet {
+        payer: AccountInfo<'info>,
+        system_program: AccountInfo<'info>,
+    },
+    /// Lamports escrowed in a program-owned account
+    Escrow(AccountInfo<'info>),
+    /// Tokens held in `from`; `signer_seeds` is empty when `authority` signs the transaction
+    Token {
+        from: AccountInfo<'info>,
+        authority: AccountInfo<'info>,
+        signer_seeds: &'a [&'a [&'a [u8]]],
+        mint: &'a Account<'info, Mint>,
+        token_program: AccountInfo<'info>,
+    },
+}
+
+impl<'a, 'info> PaymentSource<'a, 'info> {
+    /// Move `amount` to `to`: a wallet for SOL sources, a token account for token sources
+    pub fn pay(&self, to: &AccountInfo<'info>, amount: u64) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        match self {
+            PaymentSource::Wallet { payer, system_program } => anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    system_program.clone(),
+                    anchor_lang::system_program::Transfer {
+                        from: payer.clone(),
+                        to: to.clone(),
+                    },
+                ),
+                amount,
+            ),
+            PaymentSource::Escrow(escrow) => {
+                escrow.sub_lamports(amount)?;
+                to.add_lamports(amount)?;
+                Ok(())
+            }
+            PaymentSource::Token { from, authority, signer_seeds, mint, token_program } => token::transfer_checked(
+                CpiContext::new_with_signer(
+                    token_program.clone(),
+                    TransferChecked {
+                        from: from.clone(),
+                        mint: mint.to_account_info(),
+                        to: to.clone(),
+                        authority: authority.clone(),
+                    },
+                    signer_seeds,
+                ),
+                amount,
+                mint.decimals,
+            ),
+        }
+    }
+}
+
+/// Check the mint passed to an instruction against the one the sale is priced in.
+/// Returns None for SOL sales.
+pub fn payment_mint<'a, 'info>(
+    expected: Option<Pubkey>,
+    provided: &'a Option<Account<'info, Mint>>,
+) -> Result<Option<&'a Account<'info, Mint>>> {
+    match (expected, provided) {
+        (None, None) => Ok(None),
+        (Some(expected), Some(mint)) if mint.key() == expected => Ok(Some(mint)),
+        _ => err!(MarketplaceError::PaymentMintMismatch),
+    }
+}
+
+/// Where a payout to `wallet` lands: the wallet itself for SOL, otherwise its associated
+/// token account for `mint`
+pub fn payout_account<'info>(
+    wallet: &AccountInfo<'info>,
+    token_account: &Option<Account<'info, TokenAccount>>,
+    mint: Option<&Account<'info, Mint>>,
+) -> Result<AccountInfo<'info>> {
+    let mint = match mint {
+        Some(mint) => mint,
+        None => return Ok(wallet.clone()),
+    };
+
+    let token_account = token_account
+        .as_ref()
+        .ok_or(MarketplaceError::InvalidPaymentAccount)?;
+    require_keys_eq!(
+        token_account.key(),
+        get_associated_token_address(&wallet.key(), &mint.key()),
+        MarketplaceError::InvalidPaymentAccount
+    );
+
+    Ok(token_account.to_account_info())
+}
+
+/// Pay every royalty recipient its share of `split` from `source`.
+///
+/// `recipient_accounts` (the instruction's remaining accounts) carries one writable account
+/// per recipient, in RoyaltyConfig order: the recipient's wallet for SOL sales, otherwise its
+/// associated token account for `mint`.
+pub fn pay_royalties<'info>(
+    source: &PaymentSource<'_, 'info>,
+    split: &RoyaltySplit,
+    recipient_accounts: &[AccountInfo<'info>],
+    mint: Option<&Account<'info, Mint>>,
+) -> Result<()> {
+    require!(
+        recipient_accounts.len() >= split.royalties.len(),
+        MarketplaceError::RoyaltyRecipientMismatch
+    );
+
+    for (payout, account) in split.royalties.iter().zip(recipient_accounts) {
+        let expected = match mint {
+            None => payout.wallet,
+            Some(mint) => get_associated_token_address(&payout.wallet, &mint.key()),
+        };
+        require_keys_eq!(account.key(), expected, MarketplaceError::RoyaltyRecipientMismatch);
+
+        source.pay(account, payout.amount)?;
+    }
+
+    Ok(())
+}
+
+/// Account holding funds escrowed by `owner`: the owner PDA itself for SOL, otherwise its
+/// `[b"payment_escrow", owner]` token account
+pub fn escrow_account<'info>(
+    owner: &AccountInfo<'info>,
+    payment_escrow: &Option<Account<'info, TokenAccount>>,
+    mint: Option<&Account<'info, Mint>>,
+) -> Result<AccountInfo<'info>> {
+    match mint {
+        None => Ok(owner.clone()),
+        Some(_) => Ok(payment_escrow
+            .as_ref()
+            .ok_or(MarketplaceError::InvalidPaymentAccount)?
+            .to_account_info()),
+    }
+}
+
+/// Source for paying out funds escrowed by `owner`, which signs with `signer_seeds`
+pub fn escrow_source<'a, 'info>(
+    owner: &AccountInfo<'info>,
+    payment_escrow: &Option<Account<'info, TokenAccount>>,
+    mint: Option<&'a Account<'info, Mint>>,
+    signer_seeds: &'a [&'a [&'a [u8]]],
+    token_program: AccountInfo<'info>,
+) -> Result<PaymentSource<'a, 'info>> {
+    let escrow = escrow_account(owner, payment_escrow, mint)?;
+    Ok(match mint {
+        None => PaymentSource::Escrow(escrow),
+        Some(mint) => PaymentSource::Token {
+            from: escrow,
+            authority: owner.clone(),
+            signer_seeds,
+            mint,
+            token_program,
+        },
+    })
+}
+
+/// Source for a signer paying in SOL or, for token sales, from `token_account`
+pub fn wallet_source<'a, 'info>(
+    payer: &Signer<'info>,
+    token_account: &Option<Account<'info, TokenAccount>>,
+    mint: Option<&'a Account<'info, Mint>>,
+    system_program: &Program<'info, System>,
+    token_program: &Program<'info, Token>,
+) -> Result<PaymentSource<'a, 'info>> {
+    Ok(match mint {
+        None => PaymentSource::Wallet {
+            payer: payer.to_account_info(),
+            system_program: system_program.to_account_info(),
+        },
+        Some(mint) => PaymentSource::Token {
+            from: source_token_account(token_account, payer.key(), mint)?,
+            authority: payer.to_account_info(),
+            signer_seeds: &[],
+            mint,
+            token_program: token_program.to_account_info(),
+        },
+    })
+}
+
+/// Token account a buyer or bidder pays from; must hold `mint` and be owned by `owner`
+pub fn source_token_account<'info>(
+    token_account: &Option<Account<'info, TokenAccount>>,
+    owner: Pubkey,
+    mint: &Account<'info, Mint>,
+) -> Result<AccountInfo<'info>> {
+    let token_account = token_account
+        .as_ref()
+        .ok_or(MarketplaceError::InvalidPaymentAccount)?;
+    require_keys_eq!(token_account.owner, owner, MarketplaceError::InvalidPaymentAccount);
+    require_keys_eq!(token_account.mint, mint.key(), MarketplaceError::InvalidPaymentAccount);
+
+    Ok(token_account.to_account_info())
+}
+
+/// Close an emptied payment escrow token account, returning its rent to `destination`
+pub fn close_payment_escrow<'info>(
+    payment_escrow: &Option<Account<'info, TokenAccount>>,
+    destination: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    token_program: AccountInfo<'info>,
+) -> Result<()> {
+    let payment_escrow = match payment_escrow {
+        Some(payment_escrow) => payment_escrow,
+        None => return Ok(()),
+    };
+
+    token::close_account(CpiContext::new_with_signer(
+        token_program,
+        CloseAccount {
+            account: payment_escrow.to_account_info(),
+            destination,
+            authority,
+        },
+        signer_seeds,
+    ))
+}
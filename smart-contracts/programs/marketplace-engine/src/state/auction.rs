@@ -0,0 +1,352 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::*;
+
+#[account] 
+pub struct Auction {
+    pub ticket_mint: Pubkey,          // Which ticket is being auctioned
+    pub seller: Pubkey,               // Who's selling via auction
+    pub event: Pubkey,                // Event the ticket belongs to (ticket-nft-core)
+    pub payment_mint: Option<Pubkey>, // SPL mint bids are made in (None = SOL)
+    pub starting_bid: u64,            // Minimum bid to start (Dutch: opening price)
+    pub current_bid: u64,             // Current highest bid, escrowed here or in the payment escrow
+    pub highest_bidder: Option<Pubkey>, // Current winner
+    pub second_bid: u64,              // Runner-up revealed bid (sealed second-price)
+    pub sealed_bid_count: u32,        // Sealed commitments received
+    pub reserve_hash: Option<[u8; 32]>, // Hidden reserve commitment, revealed at settlement
+    pub price_cap: u64,               // Maximum bid (anti-scalping)
+    pub start_time: i64,              // When auction started
+    pub end_time: i64,                // When auction ends (pushed out by late bids)
+    pub extended_seconds: i64,        // Total anti-sniping extension applied so far
+    pub auction_type: AuctionType,    // English (bid up), Dutch (price down) or Sealed
+    pub status: AuctionStatus,        // Active/Ended/Cancelled
+    pub nonce: u64,                   // Which auction of this ticket this is (see AuctionCounter)
+    pub bump: u8,
+}
+
+/// Counts the auctions of one ticket. Auctions close when they end and the next one reuses the
+/// same address, so this outlives them and hands each one a fresh `Auction::nonce`.
+#[account]
+pub struct AuctionCounter {
+    pub ticket_mint: Pubkey,          // The ticket NFT being counted
+    pub auctions: u64,                // Auctions created so far, i.e. the nonce of the latest one
+    pub bump: u8,
+}
+
+impl AuctionCounter {
+    pub const LEN: usize = 8 + 32 + 8 + 1;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum AuctionType {
+    English { // Traditional bidding war (price goes up)
+        min_increment: BidIncrement,
+        extension_window: u32,        // Bids in the last N seconds extend the auction
+        max_extension: u32,           // Cap on total extension, in seconds
+    },
+    Dutch {   // Price starts high and drops until someone buys
+        floor_price: u64,
+        decay: PriceDecay,
+    },
+    Sealed {  // Commit hidden bids until end_time, then reveal them
+        pricing: SealedPricing,
+        reveal_duration: u32,         // Length of the reveal phase after end_time, in seconds
+    },
+}
+
+impl AuctionType {
+    pub const MAX_SIZE: usize = 1 + 8 + PriceDecay::MAX_SIZE; // Dutch variant
+}
+
+/// Minimum amount a new bid must beat the current one by
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum BidIncrement {
+    Absolute(u64),                    // Lamports, or payment mint base units
+    BasisPoints(u16),                 // Of the current bid (500 = 5%)
+}
+
+/// What the winner of a sealed-bid auction pays
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum SealedPricing {
+    FirstPrice,                       // Their own bid
+    SecondPrice,                      // The runner-up's bid (Vickrey)
+}
+
+/// How a Dutch auction moves from its opening price down to the floor
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum PriceDecay {
+    Linear,   // Continuous drop reaching the floor at end_time
+    Stepwise {
+        interval_seconds: u32,        // Price drops once per interval
+        step_amount: u64,             // Price dropped per interval
+    },
+}
+
+impl PriceDecay {
+    pub const MAX_SIZE: usize = 1 + 4 + 8; // Stepwise variant
+}
+
+/// Opening of a hidden reserve commitment, supplied at settlement
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct ReserveReveal {
+    pub reserve_price: u64,
+    pub salt: [u8; 32],
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum AuctionStatus {
+    Active,
+    Ended,
+    Cancelled,
+}
+
+impl Auction {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 33 + 8 + 8 + 33 + 8 + 4 + 33 + 8 + 8 + 8 + 8 + AuctionType::MAX_SIZE + 1 + 8 + 1; // ~295 bytes
+
+    /// How long after end_time the seller has to reveal a hidden reserve before the sale stands
+    pub const RESERVE_REVEAL_PERIOD: i64 = 24 * 3600;
+
+    /// Longest an auction can run before bidding closes (30 days)
+    pub const MAX_DURATION_HOURS: u64 = 30 * 24;
+
+    /// Commitment stored in `reserve_hash`: sha256(reserve_price_le || salt)
+    pub fn reserve_commitment(reserve_price: u64, salt: &[u8; 32]) -> [u8; 32] {
+        anchor_lang::solana_program::hash::hashv(&[&reserve_price.to_le_bytes(), salt]).to_bytes()
+    }
+
+    /// When the auction can be settled: end_time, or the end of the reveal phase for sealed bids
+    pub fn settlement_time(&self) -> i64 {
+        match &self.auction_type {
+            AuctionType::Sealed { reveal_duration, .. } => self.end_time + *reveal_duration as i64,
+            _ => self.end_time,
+        }
+    }
+
+    /// What the winner pays: the top bid, or for second-price sealed auctions the
+    /// runner-up bid (never below the starting bid or a revealed reserve)
+    pub fn clearing_price(&self, reserve_price: Option<u64>) -> u64 {
+        match &self.auction_type {
+            AuctionType::Sealed { pricing: SealedPricing::SecondPrice, .. } => self.second_bid
+                .max(self.starting_bid)
+                .max(reserve_price.unwrap_or(0))
+                .min(self.current_bid),
+            _ => self.current_bid,
+        }
+    }
+
+    /// Smallest bid that would currently be accepted
+    pub fn min_next_bid(&self) -> Result<u64> {
+        let min_increment = match &self.auction_type {
+            AuctionType::English { min_increment, .. } => min_increment,
+            _ => return err!(MarketplaceError::InvalidAuctionType),
+        };
+
+        if self.highest_bidder.is_none() {
+            return Ok(self.starting_bid);
+        }
+
+        let increment = match min_increment {
+            BidIncrement::Absolute(amount) => *amount,
+            BidIncrement::BasisPoints(bps) => self.current_bid
+                .checked_mul(*bps as u64)
+                .ok_or(MarketplaceError::ArithmeticOverflow)?
+                / 10000,
+        };
+
+        Ok(self.current_bid
+            .checked_add(increment.max(1))
+            .ok_or(MarketplaceError::ArithmeticOverflow)?)
+    }
+
+    /// Push end_time out when a bid lands inside the extension window, up to max_extension
+    pub fn apply_anti_sniping(&mut self, now: i64) {
+        let (window, max_extension) = match &self.auction_type {
+            AuctionType::English { extension_window, max_extension, .. } => {
+                (*extension_window as i64, *max_extension as i64)
+            }
+            _ => return,
+        };
+
+        if self.end_time - now >= window {
+            return;
+        }
+
+        let wanted = now + window - self.end_time;
+        let extension = wanted.min(max_extension - self.extended_seconds);
+        if extension > 0 {
+            self.end_time += extension;
+            self.extended_seconds += extension;
+        }
+    }
+
+    /// Current Dutch auction price at `now`, never below the floor
+    pub fn dutch_price(&self, now: i64) -> Result<u64> {
+        let (floor_price, decay) = match &self.auction_type {
+            AuctionType::Dutch { floor_price, decay } => (*floor_price, decay),
+            _ => return err!(MarketplaceError::InvalidAuctionType),
+        };
+
+        let duration = self.end_time.saturating_sub(self.start_time).max(1) as u128;
+        let elapsed = now.clamp(self.start_time, self.end_time).saturating_sub(self.start_time) as u128;
+        let price_range = self.starting_bid.saturating_sub(floor_price) as u128;
+
+        let drop = match decay {
+            PriceDecay::Linear => price_range
+                .checked_mul(elapsed)
+                .ok_or(MarketplaceError::ArithmeticOverflow)?
+                / duration,
+            PriceDecay::Stepwise { interval_seconds, step_amount } => {
+                let steps = elapsed / (*interval_seconds).max(1) as u128;
+                steps.saturating_mul(*step_amount as u128).min(price_range)
+            }
+        };
+
+        Ok(self.starting_bid - drop as u64)
+    }
+}
+
+/// A committed sealed bid; the deposit is held here until it is revealed or reclaimed
+#[account]
+pub struct SealedBid {
+    pub auction: Pubkey,              // Auction being bid on
+    pub auction_nonce: u64,           // `Auction::nonce` of that auction, part of the PDA seeds
+    pub bidder: Pubkey,               // Who placed the bid
+    pub event: Pubkey,                // Auction's event; a cancellation unlocks the deposit early
+    pub commitment: [u8; 32],         // sha256(amount_le || salt)
+    pub deposit: u64,                 // Amount escrowed, must cover the revealed amount
+    pub payment_mint: Option<Pubkey>, // Auction's SPL mint (None = SOL)
+    pub amount: u64,                  // Revealed bid amount
+    pub revealed: bool,
+    pub reveal_end: i64,              // Deposit can be reclaimed after this
+    pub bump: u8,
+}
+
+impl SealedBid {
+    pub const LEN: usize = 8 + 32 + 8 + 32 + 32 + 32 + 8 + 33 + 8 + 1 + 8 + 1; // ~203 bytes
+
+    pub fn commitment(amount: u64, salt: &[u8; 32]) -> [u8; 32] {
+        anchor_lang::solana_program::hash::hashv(&[&amount.to_le_bytes(), salt]).to_bytes()
+    }
+
+    /// Whether this bid was committed to the auction currently at `auction_key`, rather than
+    /// to an earlier auction of the same ticket at that address
+    pub fn is_for(&self, auction_key: &Pubkey, auction: &Auction) -> bool {
+        self.auction == *auction_key
+            && self.auction_nonce == auction.nonce
+            && self.reveal_end == auction.settlement_time()
+    }
+}
+
+/// Escrowed funds owed back to a bidder: an outbid English bid, or the top bid's change or
+/// refund at settlement. Held here until the bidder claims it, so no bid or settlement ever
+/// pays into the bidder's own accounts.
+#[account]
+pub struct BidRefund {
+    pub auction: Pubkey,              // Auction the funds were escrowed by
+    pub auction_nonce: u64,           // `Auction::nonce` of that auction, part of the PDA seeds
+    pub bidder: Pubkey,               // Who the funds are owed to
+    pub payment_mint: Option<Pubkey>, // Auction's SPL mint (None = SOL)
+    pub amount: u64,                  // Owed and not yet claimed
+    pub rent_payer: Pubkey,           // Who paid the rent of this account and its escrow
+    pub bump: u8,
+}
+
+impl BidRefund {
+    pub const LEN: usize = 8 + 32 + 8 + 32 + 33 + 8 + 32 + 1; // ~154 bytes
+
+    /// PDA of what `bidder` is owed by the auction at `auction`
+    pub fn address(auction: &Pubkey, bidder: &Pubkey, auction_nonce: u64) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"bid_refund", auction.as_ref(), bidder.as_ref(), &auction_nonce.to_le_bytes()],
+            &crate::ID,
+        ).0
+    }
+
+    /// Record `amount` more owed to `bidder` by the auction at `auction_key`, setting the
+    /// account up the first time that bidder is owed anything there. `payer` funded the
+    /// account's rent if this is that first credit, and gets it back when the refund is claimed.
+    pub fn credit(&mut self, auction_key: Pubkey, auction: &Auction, bidder: Pubkey, payer: Pubkey, bump: u8, amount: u64) -> Result<()> {
+        if self.rent_payer == Pubkey::default() {
+            self.rent_payer = payer;
+        }
+        self.auction = auction_key;
+        self.auction_nonce = auction.nonce;
+        self.bidder = bidder;
+        self.payment_mint = auction.payment_mint;
+        self.amount = self.amount
+            .checked_add(amount)
+            .ok_or(MarketplaceError::ArithmeticOverflow)?;
+        self.bump = bump;
+        Ok(())
+    }
+}
+
+#[event]
+pub struct AuctionCreated {
+    pub auction: Pubkey,
+    pub ticket_mint: Pubkey,
+    pub event: Pubkey,
+    pub seller: Pubkey,
+    pub auction_type: AuctionType,
+    pub payment_mint: Option<Pubkey>,
+    pub starting_bid: u64,
+    pub price_cap: u64,
+    pub end_time: i64,
+}
+
+#[event]
+pub struct BidPlaced {
+    pub auction: Pubkey,
+    pub bidder: Pubkey,
+    pub amount: u64,
+    pub end_time: i64,                // After any anti-sniping extension
+}
+
+#[event]
+pub struct BidRefundClaimed {
+    pub auction: Pubkey,
+    pub bidder: Pubkey,
+    pub amount: u64,                  // Refund paid out, excluding the refund account's rent
+}
+
+#[event]
+pub struct SealedBidCommitted {
+    pub auction: Pubkey,
+    pub bidder: Pubkey,
+    pub deposit: u64,
+}
+
+#[event]
+pub struct SealedBidRevealed {
+    pub auction: Pubkey,
+    pub bidder: Pubkey,
+    pub amount: u64,
+    pub leading: bool,                // Whether this bid took the lead
+}
+
+#[event]
+pub struct SealedBidWithdrawn {
+    pub auction: Pubkey,
+    pub bidder: Pubkey,
+    pub reclaimed: u64,               // Deposit returned, excluding the sealed bid account's rent
+    pub event_cancelled: bool,        // Withdrawn early because the event was called off
+}
+
+#[event]
+pub struct AuctionSettled {
+    pub auction: Pubkey,
+    pub ticket_mint: Pubkey,
+    pub seller: Pubkey,
+    pub winner: Option<Pubkey>,       // None: no bids, or the reserve wasn't met
+    pub price: u64,                   // Clearing price, zero when unsold
+    pub refunded: u64,                // Owed to the top bidder (second-price change or unmet reserve)
+}
+
+#[event]
+pub struct AuctionCancelled {
+    pub auction: Pubkey,
+    pub ticket_mint: Pubkey,
+    pub seller: Pubkey,
+    pub refunded_bidder: Option<Pubkey>, // Top bidder refunded when a cancelled event voids the auction
+    pub refunded: u64,
+}
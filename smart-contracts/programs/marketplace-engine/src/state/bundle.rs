@@ -0,0 +1,147 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::*;
+
+use super::{ListingStatus, RoyaltyPayout};
+
+/// Several tickets sold together for one price, e.g. a group of seats or a multi-event package.
+/// Each ticket sits in the bundle's associated token account for its mint until sale or cancel.
+#[account]
+pub struct BundleListing {
+    /// Who owns/is selling the tickets
+    pub seller: Pubkey,
+    /// Seller-chosen id, part of the PDA seeds
+    pub bundle_id: u64,
+    /// Escrowed tickets, in the order their accounts are passed
+    pub tickets: Vec<BundleTicket>,
+    /// Price for the whole bundle in lamports, or base units of `payment_mint`
+    pub price: u64,
+    /// SPL mint every ticket in the bundle is priced in (None = SOL)
+    pub payment_mint: Option<Pubkey>,
+    /// Sum of each ticket's own price cap
+    pub price_cap: u64,
+    /// When this listing expires (Unix timestamp)
+    pub expires_at: Option<i64>,
+    /// When listing was created
+    pub created_at: i64,
+    /// Current status
+    pub status: ListingStatus,
+    /// Bump for PDA derivation
+    pub bump: u8,
+}
+
+/// One ticket held by a bundle
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct BundleTicket {
+    pub ticket_mint: Pubkey,
+    pub event: Pubkey,                // ticket-nft-core Event, whose royalties apply
+    pub face_value: u64,              // Weight of this ticket in the per-event price split
+}
+
+impl BundleTicket {
+    pub const SIZE: usize = 32 + 32 + 8;
+}
+
+impl BundleListing {
+    pub const MAX_TICKETS: usize = 8;
+
+    /// Lamports taken from the bundle rent to pay whoever cranks `expire_bundle_listing`
+    pub const EXPIRY_BOUNTY: u64 = 10_000;
+
+    pub const LEN: usize = 8 +  // discriminator
+        32 +  // seller
+        8 +   // bundle_id
+        4 + Self::MAX_TICKETS * BundleTicket::SIZE + // tickets
+        8 +   // price
+        33 +  // payment_mint (Option<Pubkey>)
+        8 +   // price_cap
+        9 +   // expires_at (Option<i64>)
+        8 +   // created_at
+        1 +   // status
+        1;    // bump
+
+    /// Distinct events in the bundle, in order of first appearance
+    pub fn events(&self) -> Vec<Pubkey> {
+        let mut events: Vec<Pubkey> = Vec::new();
+        for ticket in &self.tickets {
+            if !events.contains(&ticket.event) {
+                events.push(ticket.event);
+            }
+        }
+        events
+    }
+
+    /// Divide the bundle price between its events in proportion to their tickets' face value.
+    /// Shares are rounded down and the last event takes the remainder, so they add up to
+    /// exactly `price`. Returned in `events()` order.
+    pub fn event_shares(&self) -> Result<Vec<(Pubkey, u64)>> {
+        let total_face: u128 = self.tickets.iter().map(|ticket| ticket.face_value as u128).sum();
+        require!(total_face > 0, MarketplaceError::InvalidBundle);
+
+        let events = self.events();
+        let mut shares = Vec::with_capacity(events.len());
+        let mut allocated: u64 = 0;
+        for (index, event) in events.iter().enumerate() {
+            let share = if index + 1 == events.len() {
+                self.price
+                    .checked_sub(allocated)
+                    .ok_or(MarketplaceError::ArithmeticOverflow)?
+            } else {
+                let face: u128 = self.tickets
+                    .iter()
+                    .filter(|ticket| ticket.event == *event)
+                    .map(|ticket| ticket.face_value as u128)
+                    .sum();
+                ((self.price as u128)
+                    .checked_mul(face)
+                    .ok_or(MarketplaceError::ArithmeticOverflow)?
+                    / total_face) as u64
+            };
+            allocated = allocated
+                .checked_add(share)
+                .ok_or(MarketplaceError::ArithmeticOverflow)?;
+            shares.push((*event, share));
+        }
+
+        Ok(shares)
+    }
+}
+
+#[event]
+pub struct BundleListed {
+    pub bundle: Pubkey,
+    pub seller: Pubkey,
+    pub ticket_mints: Vec<Pubkey>,
+    pub price: u64,
+    pub payment_mint: Option<Pubkey>,
+    pub price_cap: u64,
+    pub expires_at: Option<i64>,
+}
+
+#[event]
+pub struct BundleSold {
+    pub bundle: Pubkey,
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    pub ticket_mints: Vec<Pubkey>,
+    pub price: u64,
+    pub payment_mint: Option<Pubkey>,
+    /// Every event's royalty payouts, in `events()` order
+    pub royalties: Vec<RoyaltyPayout>,
+    pub seller_amount: u64,
+}
+
+#[event]
+pub struct BundleCancelled {
+    pub bundle: Pubkey,
+    pub seller: Pubkey,
+}
+
+#[event]
+pub struct BundleExpired {
+    pub bundle: Pubkey,
+    pub seller: Pubkey,
+    pub cranker: Pubkey,
+    /// Paid to the cranker out of the bundle rent; zero when the seller cranked it
+    pub bounty: u64,
+}
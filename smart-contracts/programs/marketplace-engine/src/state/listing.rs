@@ -1,19 +1,27 @@
 use anchor_lang::prelude::*;
 
+use super::RoyaltyPayout;
+
 #[account]
 pub struct Listing {
     /// The ticket NFT being sold
     pub ticket_mint: Pubkey,
     /// Who owns/is selling the ticket
     pub seller: Pubkey,
-    /// Sale price in lamports (SOL)
+    /// ticket-nft-core Event the ticket belongs to
+    pub event: Pubkey,
+    /// Sale price in lamports, or base units of `payment_mint`
     pub price: u64,
+    /// SPL mint the ticket is priced in (None = SOL), taken from the ticket's event
+    pub payment_mint: Option<Pubkey>,
     /// When this listing expires (Unix timestamp)
     pub expires_at: Option<i64>,
     /// Whether to accept offers below asking price
     pub allow_offers: bool,
     /// When listing was created
     pub created_at: i64,
+    /// Which listing of this ticket this is (see `ListingCounter`); offers are bound to it
+    pub nonce: u64,
     /// Current status
     pub status: ListingStatus,
     /// Original ticket price (for price cap calculation)
@@ -36,12 +44,119 @@ impl Listing {
     pub const LEN: usize = 8 +  // discriminator
         32 +  // ticket_mint
         32 +  // seller
+        32 +  // event
         8 +   // price
+        33 +  // payment_mint (Option<Pubkey>)
         9 +   // expires_at (Option<i64>)
         1 +   // allow_offers
         8 +   // created_at
+        8 +   // nonce
         1 +   // status
         8 +   // original_price
         8 +   // price_cap
         1;    // bump
+
+    /// Lamports from the listing's rent paid to whoever cranks `expire_listing`
+    pub const EXPIRY_BOUNTY: u64 = 10_000;
+
+    /// Whether the listing's expiry has passed at `now`; it can then only be cranked by
+    /// `expire_listing`, not sold
+    pub fn is_expired(&self, now: i64) -> bool {
+        matches!(self.expires_at, Some(expires) if expires <= now)
+    }
+}
+
+/// Counts the listings of one ticket. Listings close when they end and a relisting reuses the
+/// same address, so this outlives them and hands each one a fresh `Listing::nonce`.
+#[account]
+pub struct ListingCounter {
+    /// The ticket NFT being counted
+    pub ticket_mint: Pubkey,
+    /// Listings created so far, i.e. the nonce of the latest one
+    pub listings: u64,
+    /// Bump for PDA derivation
+    pub bump: u8,
+}
+
+impl ListingCounter {
+    pub const LEN: usize = 8 +  // discriminator
+        32 +  // ticket_mint
+        8 +   // listings
+        1;    // bump
+}
+
+/// How a ticket changed hands
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum SaleKind {
+    /// Bought at the listing price
+    Listing,
+    /// Accepted offer or counter offer on a listing
+    Offer,
+    /// Won at auction or bought from a Dutch auction
+    Auction,
+}
+
+#[event]
+pub struct ListingCreated {
+    pub listing: Pubkey,
+    pub ticket_mint: Pubkey,
+    pub event: Pubkey,
+    pub seller: Pubkey,
+    pub price: u64,
+    pub payment_mint: Option<Pubkey>,
+    pub price_cap: u64,
+    pub expires_at: Option<i64>,
+    pub allow_offers: bool,
+}
+
+/// Emitted with the listing's terms after the update
+#[event]
+pub struct ListingUpdated {
+    pub listing: Pubkey,
+    pub price: u64,
+    pub expires_at: Option<i64>,
+    pub allow_offers: bool,
+}
+
+#[event]
+pub struct ListingCancelled {
+    pub listing: Pubkey,
+    pub ticket_mint: Pubkey,
+    pub seller: Pubkey,
+}
+
+#[event]
+pub struct ListingExpired {
+    pub listing: Pubkey,
+    pub ticket_mint: Pubkey,
+    pub seller: Pubkey,
+    pub cranker: Pubkey,
+    /// Paid to the cranker out of the listing rent; zero when the seller cranked it
+    pub bounty: u64,
+}
+
+/// Every completed sale, whichever way it settled
+#[event]
+pub struct TicketSold {
+    pub ticket_mint: Pubkey,
+    pub event: Pubkey,
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    pub kind: SaleKind,
+    /// Sale price in lamports, or base units of `payment_mint`
+    pub price: u64,
+    pub payment_mint: Option<Pubkey>,
+    /// One entry per royalty recipient, in RoyaltyConfig order
+    pub royalties: Vec<RoyaltyPayout>,
+    pub seller_amount: u64,
+}
+
+/// Emitted once per `buy_many`, after a TicketSold for every listing bought
+#[event]
+pub struct CartPurchased {
+    pub buyer: Pubkey,
+    pub bought: Vec<Pubkey>,
+    /// Listings already sold, cancelled or expired, skipped under a partial fill
+    pub skipped: Vec<Pubkey>,
+    pub total_price: u64,
 }
@@ -1,14 +1,27 @@
 use anchor_lang::prelude::*;
 
+use crate::errors::*;
+
 #[account]
 pub struct Offer {
     /// Which listing this offer is for
     pub listing: Pubkey,
+    /// `Listing::nonce` of that listing, so a relisting at the same address doesn't inherit it
+    pub listing_nonce: u64,
     /// Who made the offer
     pub buyer: Pubkey,
-    /// Offer amount in lamports
+    /// Listed ticket's event; a cancellation lets the offer be refunded straight away
+    pub event: Pubkey,
+    /// Buyer's current offer in the listing's currency, held in escrow by this account
+    /// (or by its payment escrow token account for token-priced listings)
     pub amount: u64,
-    /// When offer expires
+    /// SPL mint of the listing at the time of the offer (None = SOL)
+    pub payment_mint: Option<Pubkey>,
+    /// Seller's latest counter price (only meaningful while CounterOffered)
+    pub counter_amount: u64,
+    /// Number of counter rounds so far
+    pub rounds: u8,
+    /// When the current round expires
     pub expires_at: i64,
     /// When offer was made
     pub created_at: i64,
@@ -29,12 +42,70 @@ pub enum OfferStatus {
 }
 
 impl Offer {
+    /// Maximum counters (seller and buyer combined) before a deal must be accepted or dropped
+    pub const MAX_ROUNDS: u8 = 6;
+
     pub const LEN: usize = 8 +  // discriminator
         32 +  // listing
+        8 +   // listing_nonce
         32 +  // buyer
+        32 +  // event
         8 +   // amount
+        33 +  // payment_mint (Option<Pubkey>)
+        8 +   // counter_amount
+        1 +   // rounds
         8 +   // expires_at
         8 +   // created_at
         1 +   // status
         1;    // bump
+
+    /// What the buyer adds to the escrow to accept the seller's standing counter
+    pub fn counter_top_up(&self) -> Result<u64> {
+        require!(self.status == OfferStatus::CounterOffered, MarketplaceError::NoCounterOffer);
+        Ok(self.counter_amount
+            .checked_sub(self.amount)
+            .ok_or(MarketplaceError::ArithmeticOverflow)?)
+    }
+}
+
+#[event]
+pub struct OfferMade {
+    pub offer: Pubkey,
+    pub listing: Pubkey,
+    pub buyer: Pubkey,
+    pub amount: u64,
+    pub payment_mint: Option<Pubkey>,
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct OfferCountered {
+    pub offer: Pubkey,
+    /// Seller asking for more, or buyer answering with a revised offer
+    pub party: Pubkey,
+    pub amount: u64,
+    pub rounds: u8,
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct OfferAccepted {
+    pub offer: Pubkey,
+    pub listing: Pubkey,
+    pub buyer: Pubkey,
+    pub seller: Pubkey,
+    /// Final price: the buyer's offer, or the seller's counter
+    pub amount: u64,
+}
+
+/// Escrow returned to the buyer and the offer closed without a sale
+#[event]
+pub struct OfferRefunded {
+    pub offer: Pubkey,
+    pub listing: Pubkey,
+    pub buyer: Pubkey,
+    pub amount: u64,
+    /// Cancelled, Rejected or Expired; still Active or CounterOffered when the listing
+    /// ended under the offer or its event was cancelled
+    pub status: OfferStatus,
 }
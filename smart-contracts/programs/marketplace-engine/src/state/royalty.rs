@@ -1,42 +1,294 @@
 use anchor_lang::prelude::*;
 
+use crate::errors::*;
+
 #[account]
 pub struct RoyaltyConfig {
-    /// The event this royalty config applies to
-    pub event_mint: Pubkey,
-    /// Artist wallet for royalty payments
-    pub artist_wallet: Pubkey,
-    /// Venue wallet for royalty payments
-    pub venue_wallet: Pubkey,
-    /// Platform wallet for fees
-    pub platform_wallet: Pubkey,
-    /// Artist royalty percentage (basis points: 1000 = 10%)
-    pub artist_percentage: u16,
-    /// Venue royalty percentage (basis points)
-    pub venue_percentage: u16,
-    /// Platform fee percentage (basis points)
-    pub platform_percentage: u16,
+    /// The ticket-nft-core Event this royalty config applies to
+    pub event: Pubkey,
+    /// Who gets a cut of every resale (artists, venue, promoters, platform, ...)
+    pub recipients: Vec<RoyaltyRecipient>,
+    /// Index into `recipients` of whoever collects the rounding dust
+    pub dust_recipient: u8,
     /// Price cap multiplier (basis points: 20000 = 200%)
     pub price_cap_multiplier: u16,
-    /// Who can modify this config
+    /// Who can modify this config (the event's authority in ticket-nft-core)
     pub authority: Pubkey,
     /// When config was created
     pub created_at: i64,
+    /// Bumped every time an update is applied
+    pub version: u32,
+    /// Update waiting out the time-lock, if any
+    pub pending_update: Option<PendingRoyaltyUpdate>,
     /// Bump for PDA derivation
     pub bump: u8,
 }
 
 impl RoyaltyConfig {
     pub const LEN: usize = 8 +  // discriminator
-        32 +  // event_mint
-        32 +  // artist_wallet
-        32 +  // venue_wallet
-        32 +  // platform_wallet
-        2 +   // artist_percentage
-        2 +   // venue_percentage
-        2 +   // platform_percentage
+        32 +  // event
+        4 + RoyaltyTerms::MAX_RECIPIENTS * RoyaltyRecipient::SIZE + // recipients
+        1 +   // dust_recipient
         2 +   // price_cap_multiplier
         32 +  // authority
         8 +   // created_at
+        4 +   // version
+        1 + PendingRoyaltyUpdate::SIZE + // pending_update
         1;    // bump
+
+    /// How long a queued update waits before it can be applied, so buyers and sellers
+    /// always see royalty changes coming
+    pub const UPDATE_DELAY: i64 = 48 * 3600;
+
+    pub fn terms(&self) -> RoyaltyTerms {
+        RoyaltyTerms {
+            recipients: self.recipients.clone(),
+            dust_recipient: self.dust_recipient,
+            price_cap_multiplier: self.price_cap_multiplier,
+        }
+    }
+
+    pub fn set_terms(&mut self, terms: &RoyaltyTerms) {
+        self.recipients = terms.recipients.clone();
+        self.dust_recipient = terms.dust_recipient;
+        self.price_cap_multiplier = terms.price_cap_multiplier;
+    }
+}
+
+/// One party paid on every resale
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct RoyaltyRecipient {
+    pub wallet: Pubkey,
+    pub bps: u16,                     // Basis points of the sale price
+}
+
+impl RoyaltyRecipient {
+    pub const SIZE: usize = 32 + 2;
+}
+
+/// The adjustable part of a RoyaltyConfig
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct RoyaltyTerms {
+    pub recipients: Vec<RoyaltyRecipient>,
+    pub dust_recipient: u8,           // Index into recipients
+    pub price_cap_multiplier: u16,    // Basis points of face value
+}
+
+impl RoyaltyTerms {
+    pub const MAX_RECIPIENTS: usize = 10;
+    pub const SIZE: usize = 4 + Self::MAX_RECIPIENTS * RoyaltyRecipient::SIZE + 1 + 2;
+
+    /// Royalties and fees together can't exceed the sale price, the dust has to go to
+    /// someone on the list, and the price cap can't force resales below face value
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            !self.recipients.is_empty() && self.recipients.len() <= Self::MAX_RECIPIENTS,
+            MarketplaceError::InvalidRoyaltyConfig
+        );
+        require!(
+            (self.dust_recipient as usize) < self.recipients.len(),
+            MarketplaceError::InvalidRoyaltyConfig
+        );
+
+        let total: u32 = self.recipients.iter().map(|recipient| recipient.bps as u32).sum();
+        require!(total <= 10000, MarketplaceError::InvalidRoyaltyConfig);
+        require!(self.price_cap_multiplier >= 10000, MarketplaceError::InvalidPriceCap);
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct PendingRoyaltyUpdate {
+    pub terms: RoyaltyTerms,
+    pub effective_at: i64,            // Earliest time apply_royalty_update succeeds
+}
+
+impl PendingRoyaltyUpdate {
+    pub const SIZE: usize = RoyaltyTerms::SIZE + 8;
+}
+
+/// Amount owed to one royalty recipient from a sale
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct RoyaltyPayout {
+    pub wallet: Pubkey,
+    pub amount: u64,
+}
+
+impl RoyaltyPayout {
+    pub const SIZE: usize = 32 + 8;
+}
+
+/// How a sale price is divided between royalty recipients and the seller
+pub struct RoyaltySplit {
+    /// One entry per recipient, in RoyaltyConfig order
+    pub royalties: Vec<RoyaltyPayout>,
+    pub seller_amount: u64,
+}
+
+impl RoyaltySplit {
+    pub fn total_royalties(&self) -> u64 {
+        self.royalties.iter().map(|payout| payout.amount).sum()
+    }
+}
+
+impl RoyaltyConfig {
+    /// Split `total_price` using this config's basis points. The combined royalty is rounded
+    /// down once; whatever per-recipient rounding leaves over goes to the dust recipient, so
+    /// the payouts and seller amount always add up to exactly `total_price`.
+    pub fn split(&self, total_price: u64) -> Result<RoyaltySplit> {
+        let bps = |basis_points: u64| -> Result<u64> {
+            let amount = (total_price as u128)
+                .checked_mul(basis_points as u128)
+                .ok_or(MarketplaceError::ArithmeticOverflow)?
+                / 10000;
+            Ok(amount as u64)
+        };
+
+        let total_bps: u64 = self.recipients.iter().map(|recipient| recipient.bps as u64).sum();
+        let total_royalty = bps(total_bps)?;
+
+        let mut royalties = self.recipients
+            .iter()
+            .map(|recipient| Ok(RoyaltyPayout {
+                wallet: recipient.wallet,
+                amount: bps(recipient.bps as u64)?,
+            }))
+            .collect::<Result<Vec<_>>>()?;
+
+        let paid: u64 = royalties.iter().map(|payout| payout.amount).sum();
+        let dust = total_royalty
+            .checked_sub(paid)
+            .ok_or(MarketplaceError::ArithmeticOverflow)?;
+        if let Some(payout) = royalties.get_mut(self.dust_recipient as usize) {
+            payout.amount = payout.amount
+                .checked_add(dust)
+                .ok_or(MarketplaceError::ArithmeticOverflow)?;
+        }
+
+        let seller_amount = total_price
+            .checked_sub(total_royalty)
+            .ok_or(MarketplaceError::ArithmeticOverflow)?;
+
+        Ok(RoyaltySplit {
+            royalties,
+            seller_amount,
+        })
+    }
+}
+
+/// Running resale totals for one event, updated by every settlement
+#[account]
+pub struct RoyaltyStats {
+    /// The event these totals cover
+    pub event: Pubkey,
+    /// Sum of all resale prices, in the event's payment currency
+    pub total_volume: u64,
+    /// Number of completed resales
+    pub sale_count: u64,
+    /// Highest resale price so far
+    pub highest_price: u64,
+    /// Every royalty paid
+    pub total_royalties: u64,
+    /// Cumulative payout per recipient slot, in RoyaltyConfig order, under the slot's latest wallet
+    pub payees: Vec<RoyaltyPayout>,
+    /// Bump for PDA derivation
+    pub bump: u8,
+}
+
+impl RoyaltyStats {
+    /// One payee per recipient slot, so config updates never run out of room
+    pub const MAX_PAYEES: usize = RoyaltyTerms::MAX_RECIPIENTS;
+
+    pub const LEN: usize = 8 +  // discriminator
+        32 +  // event
+        8 +   // total_volume
+        8 +   // sale_count
+        8 +   // highest_price
+        8 +   // total_royalties
+        4 + Self::MAX_PAYEES * RoyaltyPayout::SIZE + // payees
+        1;    // bump
+
+    /// Add a sale at `price`, paid out as `split`
+    pub fn record_sale(&mut self, price: u64, split: &RoyaltySplit) -> Result<()> {
+        self.total_volume = self.total_volume
+            .checked_add(price)
+            .ok_or(MarketplaceError::ArithmeticOverflow)?;
+        self.sale_count = self.sale_count
+            .checked_add(1)
+            .ok_or(MarketplaceError::ArithmeticOverflow)?;
+        self.highest_price = self.highest_price.max(price);
+        self.total_royalties = self.total_royalties
+            .checked_add(split.total_royalties())
+            .ok_or(MarketplaceError::ArithmeticOverflow)?;
+
+        // Payouts are tracked by the recipient's position, not its wallet, so a rotated wallet
+        // takes over its predecessor's running total instead of needing a new entry
+        for (index, payout) in split.royalties.iter().enumerate() {
+            match self.payees.get_mut(index) {
+                Some(payee) => {
+                    payee.wallet = payout.wallet;
+                    payee.amount = payee.amount
+                        .checked_add(payout.amount)
+                        .ok_or(MarketplaceError::ArithmeticOverflow)?;
+                }
+                None => self.payees.push(payout.clone()),
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn analytics(&self) -> RoyaltyAnalytics {
+        RoyaltyAnalytics {
+            event: self.event,
+            total_volume: self.total_volume,
+            sale_count: self.sale_count,
+            highest_price: self.highest_price,
+            average_price: self.total_volume.checked_div(self.sale_count).unwrap_or(0),
+            total_royalties: self.total_royalties,
+            payees: self.payees.clone(),
+        }
+    }
+}
+
+/// What `get_royalty_analytics` returns, borsh-encoded in the transaction's return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct RoyaltyAnalytics {
+    pub event: Pubkey,
+    pub total_volume: u64,
+    pub sale_count: u64,
+    pub highest_price: u64,
+    pub average_price: u64,
+    pub total_royalties: u64,
+    pub payees: Vec<RoyaltyPayout>,
+}
+
+#[event]
+pub struct RoyaltyConfigured {
+    pub royalty_config: Pubkey,
+    pub event: Pubkey,
+    pub authority: Pubkey,
+    pub terms: RoyaltyTerms,
+}
+
+#[event]
+pub struct RoyaltyUpdateQueued {
+    pub royalty_config: Pubkey,
+    pub old_terms: RoyaltyTerms,
+    pub new_terms: RoyaltyTerms,
+    pub effective_at: i64,
+}
+
+#[event]
+pub struct RoyaltyUpdateCancelled {
+    pub royalty_config: Pubkey,
+    pub cancelled_terms: RoyaltyTerms,
+}
+
+#[event]
+pub struct RoyaltyConfigUpdated {
+    pub royalty_config: Pubkey,
+    pub version: u32,
+    pub old_terms: RoyaltyTerms,
+    pub new_terms: RoyaltyTerms,
 }
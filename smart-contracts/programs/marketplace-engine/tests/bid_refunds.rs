@@ -0,0 +1,129 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::spl_token;
+
+use marketplace_engine::instructions::*;
+use marketplace_engine::state::*;
+
+use common::*;
+
+impl Sale {
+    fn place_bid(&self, outbidder: Pubkey, bid_amount: u64) -> Result<PlaceBid<'static>> {
+        let auction = self.auction_key();
+        run::<PlaceBid, 10>([
+            self.auction_account(&Auction { end_time: 3600, ..self.auction_state(english()) }),
+            TestAccount::signer(outbidder),
+            self.event(),
+            TestAccount::absent(),
+            TestAccount::absent(),
+            TestAccount::absent(),
+            TestAccount::uncreated(BidRefund::address(&auction, &self.buyer, 1)),
+            TestAccount::absent(),
+            TestAccount::program(spl_token::ID),
+            TestAccount::program(system_program::ID),
+        ], infos([]), |ctx| place_bid::handler(ctx, bid_amount))
+    }
+
+    fn claim_bid_refund(&self, rent_payer: Pubkey, passed_rent_payer: Pubkey) -> Result<ClaimBidRefund<'static>> {
+        run::<ClaimBidRefund, 7>([
+            self.bid_refund(self.buyer, PRICE, rent_payer),
+            TestAccount::signer(self.buyer),
+            TestAccount::wallet(passed_rent_payer),
+            TestAccount::absent(),
+            TestAccount::absent(),
+            TestAccount::absent(),
+            TestAccount::program(spl_token::ID),
+        ], infos([]), claim_bid_refund::handler)
+    }
+}
+
+fn english_auction(nonce: u64) -> Auction {
+    Auction { payment_mint: Some(Pubkey::new_unique()), nonce, ..auction(english()) }
+}
+
+fn empty_refund() -> BidRefund {
+    BidRefund {
+        auction: Pubkey::default(),
+        auction_nonce: 0,
+        bidder: Pubkey::default(),
+        payment_mint: None,
+        amount: 0,
+        rent_payer: Pubkey::default(),
+        bump: 0,
+    }
+}
+
+#[test]
+fn every_outbid_is_credited_to_the_same_refund() {
+    let auction_key = Pubkey::new_unique();
+    let auction = english_auction(3);
+    let bidder = Pubkey::new_unique();
+    let first_outbidder = Pubkey::new_unique();
+    let mut refund = empty_refund();
+
+    // Outbid twice in the same auction, e.g. after raising back into the lead
+    refund.credit(auction_key, &auction, bidder, first_outbidder, 254, 100).unwrap();
+    refund.credit(auction_key, &auction, bidder, Pubkey::new_unique(), 254, 250).unwrap();
+
+    assert_eq!(refund.amount, 350);
+    assert_eq!(refund.auction, auction_key);
+    assert_eq!(refund.auction_nonce, 3);
+    assert_eq!(refund.bidder, bidder);
+    assert_eq!(refund.payment_mint, auction.payment_mint);
+    assert_eq!(refund.rent_payer, first_outbidder);
+    assert_eq!(refund.bump, 254);
+}
+
+#[test]
+fn a_credit_that_would_overflow_is_refused() {
+    let auction = english_auction(1);
+    let mut refund = empty_refund();
+    refund.amount = u64::MAX;
+
+    assert!(refund.credit(Pubkey::new_unique(), &auction, Pubkey::new_unique(), Pubkey::new_unique(), 255, 1).is_err());
+    assert_eq!(refund.amount, u64::MAX);
+}
+
+#[test]
+fn refunds_are_kept_apart_per_bidder_and_per_auction_of_the_ticket() {
+    let auction_key = Pubkey::new_unique();
+    let bidder = Pubkey::new_unique();
+
+    let refund = BidRefund::address(&auction_key, &bidder, 1);
+    assert_ne!(refund, BidRefund::address(&auction_key, &Pubkey::new_unique(), 1));
+    assert_ne!(refund, BidRefund::address(&auction_key, &bidder, 2));
+    assert_eq!(
+        refund,
+        Pubkey::find_program_address(
+            &[b"bid_refund", auction_key.as_ref(), bidder.as_ref(), &1u64.to_le_bytes()],
+            &marketplace_engine::ID,
+        ).0
+    );
+}
+
+#[test]
+fn the_outbidder_pays_the_refund_rent_and_gets_it_back_on_claim() {
+    let sale = Sale::new();
+    let outbidder = Pubkey::new_unique();
+    let rent = Rent::default().minimum_balance(BidRefund::LEN);
+
+    let bid = sale.place_bid(outbidder, 2 * PRICE).unwrap();
+    let refund = bid.previous_bidder_refund.unwrap();
+    assert_eq!(refund.amount, PRICE);
+    assert_eq!(refund.rent_payer, outbidder);
+    assert_eq!(refund.to_account_info().lamports(), rent + PRICE);
+    assert_eq!(bid.bidder.lamports(), LAMPORTS - rent - 2 * PRICE);
+
+    // The outbid bidder claims exactly their bid; the rent goes back to the outbidder
+    let claimed = sale.claim_bid_refund(outbidder, outbidder).unwrap();
+    assert_eq!(claimed.bidder.lamports(), LAMPORTS + PRICE);
+    assert_eq!(claimed.rent_payer.lamports(), LAMPORTS + rent);
+    assert_eq!(claimed.bid_refund.to_account_info().lamports(), 0);
+
+    assert_eq!(
+        sale.claim_bid_refund(outbidder, Pubkey::new_unique()).err(),
+        Some(ErrorCode::ConstraintRaw.into())
+    );
+}
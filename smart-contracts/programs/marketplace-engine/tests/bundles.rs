@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+
+use marketplace_engine::errors::MarketplaceError;
+use marketplace_engine::state::*;
+
+fn bundle(price: u64, tickets: &[(Pubkey, u64)]) -> BundleListing {
+    BundleListing {
+        seller: Pubkey::new_unique(),
+        bundle_id: 1,
+        tickets: tickets
+            .iter()
+            .map(|&(event, face_value)| BundleTicket { ticket_mint: Pubkey::new_unique(), event, face_value })
+            .collect(),
+        price,
+        payment_mint: None,
+        price_cap: price,
+        expires_at: None,
+        created_at: 0,
+        status: ListingStatus::Active,
+        bump: 255,
+    }
+}
+
+#[test]
+fn shares_follow_each_events_face_value_with_the_remainder_on_the_last() {
+    let (a, b, c) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+
+    // Two tickets of `a`, one each of `b` and `c`, listed out of event order
+    let bundle = bundle(1_001, &[(a, 100), (b, 150), (a, 100), (c, 50)]);
+    let shares = bundle.event_shares().unwrap();
+
+    // 1_001 * 200/400 = 500.5, 1_001 * 150/400 = 375.375, and `c` takes what is left
+    assert!(shares == vec![(a, 500), (b, 375), (c, 126)]);
+}
+
+#[test]
+fn a_single_event_takes_the_whole_price() {
+    let event = Pubkey::new_unique();
+    let bundle = bundle(999, &[(event, 30), (event, 70), (event, 1)]);
+    assert!(bundle.event_shares().unwrap() == vec![(event, 999)]);
+}
+
+#[test]
+fn shares_always_sum_exactly_to_the_price() {
+    let events: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+    let tickets = [
+        (events[0], 3),
+        (events[1], 7),
+        (events[1], 7),
+        (events[2], 11),
+        (events[0], 3),
+        (events[2], 13),
+        (events[1], 1),
+    ];
+
+    for price in [0, 1, 2, 44, 1_000, 999_999_999, u64::MAX / 2, u64::MAX] {
+        let shares = bundle(price, &tickets).event_shares().unwrap();
+        let total: u128 = shares.iter().map(|&(_, share)| share as u128).sum();
+        assert_eq!(total, price as u128);
+        assert_eq!(shares.iter().map(|&(event, _)| event).collect::<Vec<_>>(), events);
+    }
+}
+
+#[test]
+fn a_bundle_with_no_face_value_cannot_be_split() {
+    let bundle = bundle(1_000, &[(Pubkey::new_unique(), 0), (Pubkey::new_unique(), 0)]);
+    assert_eq!(bundle.event_shares().err(), Some(MarketplaceError::InvalidBundle.into()));
+}
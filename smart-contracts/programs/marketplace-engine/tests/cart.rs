@@ -0,0 +1,115 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::spl_token;
+
+use marketplace_engine::errors::MarketplaceError;
+use marketplace_engine::instructions::*;
+use marketplace_engine::state::*;
+
+use common::*;
+
+/// Accounts `buy_many` takes per SOL listing, including the artist's royalty wallet
+const GROUP: usize = 8;
+
+impl Sale {
+    /// The sale's accounts in a SOL cart, with `listing` standing in for its listing
+    fn cart_group(&self, listing: TestAccount) -> [TestAccount; GROUP] {
+        [
+            listing,
+            TestAccount::wallet(self.seller),
+            self.listing_escrow(1),
+            self.buyer_ticket_account(),
+            self.event(),
+            self.royalty_config(self.event),
+            self.royalty_stats(self.event),
+            TestAccount::wallet(self.artist),
+        ]
+    }
+
+    fn expired_listing(&self) -> TestAccount {
+        self.listing_account(&Listing { expires_at: Some(0), ..self.listing_state() })
+    }
+
+    /// A listing that has already sold, and so been closed
+    fn sold_listing(&self) -> TestAccount {
+        TestAccount::uncreated(self.listing_key())
+    }
+
+    fn buy_many(&self, cart: &'static [AccountInfo<'static>], listing_count: u8, allow_partial: bool) -> Result<BuyMany<'static>> {
+        run::<BuyMany, 5>([
+            TestAccount::signer(self.buyer),
+            TestAccount::absent(),
+            TestAccount::absent(),
+            TestAccount::program(spl_token::ID),
+            TestAccount::program(system_program::ID),
+        ], cart, |ctx| buy_many::handler(ctx, listing_count, allow_partial))
+    }
+}
+
+/// Tickets the buyer holds from the `index`th listing of `cart`
+fn bought(cart: &[AccountInfo], index: usize) -> u64 {
+    token_amount(&cart[index * GROUP + 3])
+}
+
+/// The buyer's cart: two listings still for sale around one that sold and one that expired
+fn mixed_cart(sale: &Sale) -> &'static [AccountInfo<'static>] {
+    let (sold, expired, last) = (sale.another_ticket(), sale.another_ticket(), sale.another_ticket());
+    infos(
+        [
+            sale.cart_group(sale.listing()),
+            sold.cart_group(sold.sold_listing()),
+            expired.cart_group(expired.expired_listing()),
+            last.cart_group(last.listing()),
+        ]
+        .into_iter()
+        .flatten(),
+    )
+}
+
+#[test]
+fn a_partial_fill_skips_sold_and_expired_listings() {
+    let sale = Sale::new();
+    let cart = mixed_cart(&sale);
+
+    let cart_bought = sale.buy_many(cart, 4, true).unwrap();
+    assert_eq!(cart_bought.buyer.lamports(), LAMPORTS - 2 * PRICE);
+    assert_eq!((0..4).map(|index| bought(cart, index)).collect::<Vec<_>>(), vec![1, 0, 0, 1]);
+
+    // The expired listing's ticket stays in escrow for `expire_listing`
+    assert_eq!(token_amount(&cart[2 * GROUP + 2]), 1);
+}
+
+#[test]
+fn without_a_partial_fill_the_cart_is_all_or_nothing() {
+    let sale = Sale::new();
+    let not_active = Some(MarketplaceError::ListingNotActive.into());
+
+    assert_eq!(sale.buy_many(mixed_cart(&sale), 4, false).err(), not_active);
+
+    let expired = sale.another_ticket();
+    let cart = infos([sale.cart_group(sale.listing()), expired.cart_group(expired.expired_listing())].into_iter().flatten());
+    assert_eq!(sale.buy_many(cart, 2, false).err(), not_active);
+
+    // Nothing left to buy fails even a partial fill
+    let sold = sale.another_ticket();
+    let cart = infos([sold.cart_group(sold.sold_listing()), expired.cart_group(expired.expired_listing())].into_iter().flatten());
+    assert_eq!(sale.buy_many(cart, 2, true).err(), Some(MarketplaceError::NothingPurchased.into()));
+}
+
+#[test]
+fn a_cart_holds_one_to_max_cart_size_listings() {
+    let sale = Sale::new();
+    let tickets: Vec<Sale> = (0..=MAX_CART_SIZE).map(|_| sale.another_ticket()).collect();
+    let cart = infos(tickets.iter().flat_map(|ticket| ticket.cart_group(ticket.listing())));
+    let invalid = Some(MarketplaceError::InvalidCart.into());
+
+    assert_eq!(sale.buy_many(cart, 0, false).err(), invalid);
+    assert_eq!(sale.buy_many(cart, MAX_CART_SIZE as u8 + 1, false).err(), invalid);
+
+    let full = sale.buy_many(cart, MAX_CART_SIZE as u8, false).unwrap();
+    assert_eq!(full.buyer.lamports(), LAMPORTS - MAX_CART_SIZE as u64 * PRICE);
+    assert_eq!(bought(cart, MAX_CART_SIZE - 1), 1);
+    assert_eq!(bought(cart, MAX_CART_SIZE), 0);
+}
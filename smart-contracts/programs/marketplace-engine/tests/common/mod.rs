@@ -0,0 +1,590 @@
+//! Fixtures shared by the marketplace tests, and a stand-in runtime that runs instructions
+//! through their `try_accounts` validation and handler the way the program entrypoint does.
+
+#![allow(dead_code)]
+
+use std::collections::BTreeSet;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::{ProgramResult, SUCCESS};
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program_error::ProgramError;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::solana_program::{program_stubs, sysvar};
+use anchor_lang::system_program;
+use anchor_lang::Bumps;
+use anchor_spl::token::spl_token;
+use anchor_spl::token::spl_token::instruction::TokenInstruction;
+
+use marketplace_engine::state::*;
+use ticket_nft_core::state::{Event, TicketData};
+
+pub const PRICE: u64 = 1_000_000_000;
+
+/// Lamports every test account starts with unless set otherwise
+pub const LAMPORTS: u64 = 10 * PRICE;
+
+pub fn recipients(bps: &[u16]) -> Vec<RoyaltyRecipient> {
+    bps.iter()
+        .map(|&bps| RoyaltyRecipient { wallet: Pubkey::new_unique(), bps })
+        .collect()
+}
+
+pub fn royalty_config(event: Pubkey, recipients: Vec<RoyaltyRecipient>) -> RoyaltyConfig {
+    RoyaltyConfig {
+        event,
+        recipients,
+        dust_recipient: 0,
+        price_cap_multiplier: 20_000,
+        authority: Pubkey::new_unique(),
+        created_at: 0,
+        version: 1,
+        pending_update: None,
+        bump: 255,
+    }
+}
+
+pub fn royalty_stats(event: Pubkey) -> RoyaltyStats {
+    RoyaltyStats {
+        event,
+        total_volume: 0,
+        sale_count: 0,
+        highest_price: 0,
+        total_royalties: 0,
+        payees: vec![],
+        bump: 255,
+    }
+}
+
+pub fn listing() -> Listing {
+    Listing {
+        ticket_mint: Pubkey::new_unique(),
+        seller: Pubkey::new_unique(),
+        event: Pubkey::new_unique(),
+        price: PRICE,
+        payment_mint: None,
+        expires_at: None,
+        allow_offers: true,
+        created_at: 0,
+        nonce: 1,
+        status: ListingStatus::Active,
+        original_price: PRICE,
+        price_cap: 2 * PRICE,
+        bump: 255,
+    }
+}
+
+pub fn offer(amount: u64, counter_amount: u64, status: OfferStatus) -> Offer {
+    Offer {
+        listing: Pubkey::new_unique(),
+        listing_nonce: 1,
+        buyer: Pubkey::new_unique(),
+        event: Pubkey::new_unique(),
+        amount,
+        payment_mint: None,
+        counter_amount,
+        rounds: 1,
+        expires_at: i64::MAX,
+        created_at: 0,
+        status,
+        bump: 255,
+    }
+}
+
+pub fn english() -> AuctionType {
+    AuctionType::English {
+        min_increment: BidIncrement::BasisPoints(500),
+        extension_window: 300,
+        max_extension: 600,
+    }
+}
+
+pub fn auction(auction_type: AuctionType) -> Auction {
+    Auction {
+        ticket_mint: Pubkey::new_unique(),
+        seller: Pubkey::new_unique(),
+        event: Pubkey::new_unique(),
+        payment_mint: None,
+        starting_bid: PRICE,
+        current_bid: PRICE,
+        highest_bidder: None,
+        second_bid: 0,
+        sealed_bid_count: 0,
+        reserve_hash: None,
+        price_cap: 2 * PRICE,
+        start_time: 0,
+        end_time: 3600,
+        extended_seconds: 0,
+        auction_type,
+        status: AuctionStatus::Active,
+        nonce: 1,
+        bump: 255,
+    }
+}
+
+#[repr(C)]
+pub struct TestAccount {
+    /// Read by `AccountInfo::realloc` from just ahead of the key, as the runtime lays it out
+    original_data_len: u32,
+    pub key: Pubkey,
+    pub owner: Pubkey,
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub signer: bool,
+    pub executable: bool,
+}
+
+impl TestAccount {
+    pub fn new(key: Pubkey, owner: Pubkey, data: Vec<u8>) -> Self {
+        Self {
+            original_data_len: data.len() as u32,
+            key,
+            owner,
+            lamports: LAMPORTS,
+            data,
+            signer: false,
+            executable: false,
+        }
+    }
+
+    pub fn wallet(key: Pubkey) -> Self {
+        Self::new(key, system_program::ID, vec![])
+    }
+
+    pub fn signer(key: Pubkey) -> Self {
+        Self { signer: true, ..Self::wallet(key) }
+    }
+
+    /// Account an instruction creates with `init`
+    pub fn uncreated(key: Pubkey) -> Self {
+        Self { lamports: 0, ..Self::wallet(key) }
+    }
+
+    pub fn program(key: Pubkey) -> Self {
+        Self { executable: true, ..Self::new(key, Pubkey::new_unique(), vec![]) }
+    }
+
+    /// Stand-in for an optional account left out of the instruction
+    pub fn absent() -> Self {
+        Self::new(marketplace_engine::ID, Pubkey::default(), vec![])
+    }
+
+    pub fn owned_by<T: AccountSerialize>(owner: Pubkey, key: Pubkey, account: &T) -> Self {
+        let mut data = vec![];
+        account.try_serialize(&mut data).unwrap();
+        Self::new(key, owner, data)
+    }
+
+    pub fn program_account<T: AccountSerialize>(key: Pubkey, account: &T) -> Self {
+        Self::owned_by(marketplace_engine::ID, key, account)
+    }
+
+    pub fn mint(key: Pubkey) -> Self {
+        let mut data = vec![0; spl_token::state::Mint::LEN];
+        spl_token::state::Mint { supply: 1, is_initialized: true, ..Default::default() }
+            .pack_into_slice(&mut data);
+        Self::new(key, spl_token::ID, data)
+    }
+
+    pub fn token_account(key: Pubkey, mint: Pubkey, owner: Pubkey, amount: u64) -> Self {
+        let mut data = vec![0; spl_token::state::Account::LEN];
+        spl_token::state::Account {
+            mint,
+            owner,
+            amount,
+            state: spl_token::state::AccountState::Initialized,
+            ..Default::default()
+        }
+        .pack_into_slice(&mut data);
+        Self::new(key, spl_token::ID, data)
+    }
+
+    pub fn rent() -> Self {
+        let rent = Rent::default();
+        let mut data = rent.lamports_per_byte_year.to_le_bytes().to_vec();
+        data.extend(rent.exemption_threshold.to_le_bytes());
+        data.push(rent.burn_percent);
+        Self::new(sysvar::rent::ID, sysvar::ID, data)
+    }
+
+    pub fn with_lamports(self, lamports: u64) -> Self {
+        Self { lamports, ..self }
+    }
+
+    /// Pad the data out to the `space` the account was created with
+    pub fn with_space(mut self, space: usize) -> Self {
+        self.data.resize(space, 0);
+        self.original_data_len = space as u32;
+        self
+    }
+}
+
+/// Account data laid out as the runtime serializes it: `realloc` records the new length in
+/// the 8 bytes ahead of the data, so every buffer is allocated with room for that
+fn account_data(data: &[u8]) -> &'static mut [u8] {
+    let words = Vec::leak(vec![0u64; 1 + data.len().div_ceil(8)]);
+    let bytes = unsafe { std::slice::from_raw_parts_mut(words.as_mut_ptr().add(1) as *mut u8, data.len()) };
+    bytes.copy_from_slice(data);
+    bytes
+}
+
+/// Account infos over `accounts`, leaked so instructions can borrow them for `'static`
+pub fn infos(accounts: impl IntoIterator<Item = TestAccount>) -> &'static [AccountInfo<'static>] {
+    let accounts = Vec::leak(accounts.into_iter().collect());
+    let infos = accounts
+        .iter_mut()
+        .map(|a| AccountInfo {
+            key: &a.key,
+            is_signer: a.signer,
+            is_writable: true,
+            lamports: std::rc::Rc::new(std::cell::RefCell::new(&mut a.lamports)),
+            data: std::rc::Rc::new(std::cell::RefCell::new(account_data(&a.data))),
+            owner: &a.owner,
+            executable: a.executable,
+            rent_epoch: 0,
+        })
+        .collect::<Vec<_>>();
+    Vec::leak(infos)
+}
+
+pub fn token_amount(info: &AccountInfo) -> u64 {
+    spl_token::state::Account::unpack(&info.data.borrow()).unwrap().amount
+}
+
+/// Clock and Rent for `Sysvar::get`, with the clock at 0, and the system and token program
+/// instructions the marketplace invokes applied to the accounts passed along
+struct Runtime;
+
+impl program_stubs::SyscallStubs for Runtime {
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { *(var_addr as *mut Clock) = Clock::default() };
+        SUCCESS
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { *(var_addr as *mut Rent) = Rent::default() };
+        SUCCESS
+    }
+
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        _signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let account = |index: usize| {
+            let key = instruction.accounts[index].pubkey;
+            account_infos
+                .iter()
+                .find(|info| *info.key == key)
+                .ok_or(ProgramError::NotEnoughAccountKeys)
+        };
+        let data = &instruction.data;
+        let u64_at = |offset: usize| u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
+
+        if instruction.program_id == system_program::ID {
+            match u32::from_le_bytes(data[..4].try_into().unwrap()) {
+                // CreateAccount { lamports, space, owner }
+                0 => {
+                    let created = account(1)?;
+                    move_lamports(account(0)?, created, u64_at(4))?;
+                    *created.try_borrow_mut_data()? = account_data(&vec![0; u64_at(12) as usize]);
+                    created.assign(&Pubkey::try_from(&data[20..52]).unwrap());
+                },
+                // Assign { owner }
+                1 => account(0)?.assign(&Pubkey::try_from(&data[4..36]).unwrap()),
+                // Transfer { lamports }
+                2 => move_lamports(account(0)?, account(1)?, u64_at(4))?,
+                // Allocate { space }
+                8 => *account(0)?.try_borrow_mut_data()? = account_data(&vec![0; u64_at(4) as usize]),
+                _ => {},
+            }
+        } else if instruction.program_id == spl_token::ID {
+            match TokenInstruction::unpack(data)? {
+                TokenInstruction::Transfer { amount } => move_tokens(account(0)?, account(1)?, amount)?,
+                TokenInstruction::TransferChecked { amount, .. } => move_tokens(account(0)?, account(2)?, amount)?,
+                TokenInstruction::InitializeAccount3 { owner } => {
+                    let mut token_account = account(0)?.try_borrow_mut_data()?;
+                    spl_token::state::Account {
+                        mint: *account(1)?.key,
+                        owner,
+                        state: spl_token::state::AccountState::Initialized,
+                        ..Default::default()
+                    }
+                    .pack_into_slice(&mut token_account);
+                },
+                TokenInstruction::CloseAccount => {
+                    let closed = account(0)?;
+                    if token_amount(closed) != 0 {
+                        return Err(ProgramError::InvalidAccountData);
+                    }
+                    move_lamports(closed, account(1)?, closed.lamports())?;
+                    *closed.try_borrow_mut_data()? = account_data(&[]);
+                    closed.assign(&system_program::ID);
+                },
+                _ => {},
+            }
+        }
+        Ok(())
+    }
+}
+
+fn move_lamports(from: &AccountInfo, to: &AccountInfo, lamports: u64) -> ProgramResult {
+    let remaining = from.lamports().checked_sub(lamports).ok_or(ProgramError::InsufficientFunds)?;
+    **from.try_borrow_mut_lamports()? = remaining;
+    **to.try_borrow_mut_lamports()? += lamports;
+    Ok(())
+}
+
+fn move_tokens(from: &AccountInfo, to: &AccountInfo, amount: u64) -> ProgramResult {
+    let mut source = spl_token::state::Account::unpack(&from.data.borrow())?;
+    source.amount = source.amount.checked_sub(amount).ok_or(ProgramError::InsufficientFunds)?;
+    source.pack_into_slice(&mut from.try_borrow_mut_data()?);
+
+    let mut destination = spl_token::state::Account::unpack(&to.data.borrow())?;
+    destination.amount += amount;
+    destination.pack_into_slice(&mut to.try_borrow_mut_data()?);
+    Ok(())
+}
+
+/// Build `T` from `accounts` the way the program entrypoint would, running its constraints
+pub fn accounts<T, const N: usize>(accounts: [TestAccount; N]) -> Result<(T, T::Bumps)>
+where
+    T: Bumps + Accounts<'static, T::Bumps>,
+    T::Bumps: Default,
+{
+    static RUNTIME: std::sync::Once = std::sync::Once::new();
+    RUNTIME.call_once(|| {
+        program_stubs::set_syscall_stubs(Box::new(Runtime));
+    });
+
+    let mut bumps = Default::default();
+    let accounts = T::try_accounts(
+        &marketplace_engine::ID,
+        &mut infos(accounts),
+        &[],
+        &mut bumps,
+        &mut BTreeSet::new(),
+    )?;
+    Ok((accounts, bumps))
+}
+
+/// Run an instruction end to end: validate `accounts`, call `handler` with `remaining`, then
+/// write the accounts back (and close those marked `close`). Returns the accounts to inspect.
+pub fn run<T, const N: usize>(
+    accounts: [TestAccount; N],
+    remaining: &'static [AccountInfo<'static>],
+    handler: impl FnOnce(Context<'_, '_, 'static, 'static, T>) -> Result<()>,
+) -> Result<T>
+where
+    T: Bumps + Accounts<'static, T::Bumps> + AccountsExit<'static>,
+    T::Bumps: Default,
+{
+    let (mut accounts, bumps) = self::accounts::<T, N>(accounts)?;
+    handler(Context::new(&marketplace_engine::ID, &mut accounts, remaining, bumps))?;
+    accounts.exit(&marketplace_engine::ID)?;
+    Ok(accounts)
+}
+
+pub fn pda(seeds: &[&[u8]]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(seeds, &marketplace_engine::ID)
+}
+
+/// A ticket of `event` up for sale by `seller` to `buyer`, under a RoyaltyConfig paying
+/// `artist` 10%
+pub struct Sale {
+    pub event: Pubkey,
+    pub event_cancelled: bool,
+    pub ticket_mint: Pubkey,
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    pub artist: Pubkey,
+}
+
+impl Sale {
+    pub fn new() -> Self {
+        Self {
+            event: Pubkey::new_unique(),
+            event_cancelled: false,
+            ticket_mint: Pubkey::new_unique(),
+            seller: Pubkey::new_unique(),
+            buyer: Pubkey::new_unique(),
+            artist: Pubkey::new_unique(),
+        }
+    }
+
+    /// Another ticket of the same event, listed by another seller to the same buyer
+    pub fn another_ticket(&self) -> Self {
+        Self {
+            ticket_mint: Pubkey::new_unique(),
+            seller: Pubkey::new_unique(),
+            ..*self
+        }
+    }
+
+    pub fn event(&self) -> TestAccount {
+        TestAccount::owned_by(ticket_nft_core::ID, self.event, &Event {
+            authority: Pubkey::new_unique(),
+            event_id: 0,
+            name: String::new(),
+            venue: String::new(),
+            start_time: 0,
+            end_time: 0,
+            tiers: vec![],
+            payment_mint: None,
+            total_supply: 0,
+            tickets_minted: 0,
+            cancelled: self.event_cancelled,
+            created_at: 0,
+            bump: 0,
+        })
+    }
+
+    pub fn ticket_data(&self) -> TestAccount {
+        let (key, bump) = Pubkey::find_program_address(
+            &[b"ticket", self.ticket_mint.as_ref()],
+            &ticket_nft_core::ID,
+        );
+        TestAccount::owned_by(ticket_nft_core::ID, key, &TicketData {
+            event: self.event,
+            mint: self.ticket_mint,
+            tier: 0,
+            section: String::new(),
+            row: String::new(),
+            seat: String::new(),
+            face_value: PRICE,
+            payment_mint: None,
+            minted_at: 0,
+            bump,
+        })
+    }
+
+    pub fn ticket_mint(&self) -> TestAccount {
+        TestAccount::mint(self.ticket_mint)
+    }
+
+    /// Token account of `owner` holding `amount` of the ticket
+    pub fn ticket_account(&self, key: Pubkey, owner: Pubkey, amount: u64) -> TestAccount {
+        TestAccount::token_account(key, self.ticket_mint, owner, amount)
+    }
+
+    pub fn buyer_ticket_account(&self) -> TestAccount {
+        self.ticket_account(Pubkey::new_unique(), self.buyer, 0)
+    }
+
+    /// RoyaltyConfig PDA of `event`: 10% to the artist for the sale's own event, nothing otherwise
+    pub fn royalty_config(&self, event: Pubkey) -> TestAccount {
+        let (key, bump) = pda(&[b"royalty_config", event.as_ref()]);
+        let bps = if event == self.event { 1_000 } else { 0 };
+        TestAccount::program_account(key, &RoyaltyConfig {
+            bump,
+            ..royalty_config(event, vec![RoyaltyRecipient { wallet: self.artist, bps }])
+        })
+    }
+
+    pub fn royalty_stats(&self, event: Pubkey) -> TestAccount {
+        let (key, bump) = pda(&[b"royalty_stats", event.as_ref()]);
+        TestAccount::program_account(key, &RoyaltyStats { bump, ..royalty_stats(event) })
+            .with_space(RoyaltyStats::LEN)
+    }
+
+    pub fn listing_key(&self) -> Pubkey {
+        pda(&[b"listing", self.ticket_mint.as_ref()]).0
+    }
+
+    /// The sale's listing, active and without expiry
+    pub fn listing_state(&self) -> Listing {
+        Listing {
+            ticket_mint: self.ticket_mint,
+            seller: self.seller,
+            event: self.event,
+            bump: pda(&[b"listing", self.ticket_mint.as_ref()]).1,
+            ..listing()
+        }
+    }
+
+    pub fn listing(&self) -> TestAccount {
+        self.listing_account(&self.listing_state())
+    }
+
+    pub fn listing_account(&self, listing: &Listing) -> TestAccount {
+        TestAccount::program_account(self.listing_key(), listing)
+    }
+
+    pub fn listing_escrow(&self, amount: u64) -> TestAccount {
+        let listing = self.listing_key();
+        self.ticket_account(pda(&[b"escrow", listing.as_ref()]).0, listing, amount)
+    }
+
+    pub fn offer_key(&self) -> Pubkey {
+        let listing = self.listing_key();
+        pda(&[b"offer", listing.as_ref(), self.buyer.as_ref(), &1u64.to_le_bytes()]).0
+    }
+
+    /// The buyer's offer of the listing price, escrowed in the offer account
+    pub fn offer(&self, status: OfferStatus) -> TestAccount {
+        let listing = self.listing_key();
+        let (key, bump) = pda(&[b"offer", listing.as_ref(), self.buyer.as_ref(), &1u64.to_le_bytes()]);
+        let rent = Rent::default().minimum_balance(Offer::LEN);
+        TestAccount::program_account(key, &Offer {
+            listing,
+            buyer: self.buyer,
+            event: self.event,
+            bump,
+            ..offer(PRICE, PRICE, status)
+        })
+        .with_lamports(rent + PRICE)
+    }
+
+    pub fn auction_key(&self) -> Pubkey {
+        pda(&[b"auction", self.ticket_mint.as_ref()]).0
+    }
+
+    /// The sale's auction, ended at 0 with the buyer's bid of `PRICE` on top
+    pub fn auction_state(&self, auction_type: AuctionType) -> Auction {
+        Auction {
+            ticket_mint: self.ticket_mint,
+            seller: self.seller,
+            event: self.event,
+            highest_bidder: Some(self.buyer),
+            end_time: 0,
+            bump: pda(&[b"auction", self.ticket_mint.as_ref()]).1,
+            ..auction(auction_type)
+        }
+    }
+
+    pub fn auction(&self, auction_type: AuctionType) -> TestAccount {
+        self.auction_account(&self.auction_state(auction_type))
+    }
+
+    /// The auction account, holding the escrowed top bid on top of its rent
+    pub fn auction_account(&self, auction: &Auction) -> TestAccount {
+        let rent = Rent::default().minimum_balance(Auction::LEN);
+        let escrowed = if auction.highest_bidder.is_some() { auction.current_bid } else { 0 };
+        TestAccount::program_account(self.auction_key(), auction)
+            .with_space(Auction::LEN)
+            .with_lamports(rent + escrowed)
+    }
+
+    /// Refund of `amount` owed to `bidder` by the sale's auction, its rent paid by `rent_payer`
+    pub fn bid_refund(&self, bidder: Pubkey, amount: u64, rent_payer: Pubkey) -> TestAccount {
+        let auction = self.auction_key();
+        let (key, bump) = pda(&[b"bid_refund", auction.as_ref(), bidder.as_ref(), &1u64.to_le_bytes()]);
+        let rent = Rent::default().minimum_balance(BidRefund::LEN);
+        TestAccount::program_account(key, &BidRefund {
+            auction,
+            auction_nonce: 1,
+            bidder,
+            payment_mint: None,
+            amount,
+            rent_payer,
+            bump,
+        })
+        .with_lamports(rent + amount)
+    }
+
+    pub fn auction_escrow(&self, amount: u64) -> TestAccount {
+        let auction = self.auction_key();
+        self.ticket_account(pda(&[b"auction_escrow", auction.as_ref()]).0, auction, amount)
+    }
+}
@@ -0,0 +1,116 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::spl_token;
+
+use marketplace_engine::errors::MarketplaceError;
+use marketplace_engine::instructions::*;
+use marketplace_engine::state::*;
+
+use common::*;
+
+/// Dutch auction from 1_000 down to a floor of 100 over an hour
+fn dutch(decay: PriceDecay) -> Auction {
+    Auction {
+        starting_bid: 1_000,
+        current_bid: 1_000,
+        price_cap: 2_000,
+        ..auction(AuctionType::Dutch { floor_price: 100, decay })
+    }
+}
+
+impl Sale {
+    /// Buy from a linear Dutch auction of `PRICE` down to `PRICE / 2`, run from `start_time`
+    /// to `end_time` around the clock at 0
+    fn buy_dutch(&self, start_time: i64, end_time: i64, artist: &'static [AccountInfo<'static>]) -> Result<BuyDutch<'static>> {
+        let auction = Auction {
+            highest_bidder: None,
+            start_time,
+            end_time,
+            ..self.auction_state(AuctionType::Dutch { floor_price: PRICE / 2, decay: PriceDecay::Linear })
+        };
+        run::<BuyDutch, 14>([
+            self.auction_account(&auction),
+            TestAccount::signer(self.buyer),
+            TestAccount::wallet(self.seller),
+            self.buyer_ticket_account(),
+            self.auction_escrow(1),
+            self.event(),
+            self.royalty_config(self.event),
+            self.royalty_stats(self.event),
+            TestAccount::absent(),
+            TestAccount::absent(),
+            TestAccount::absent(),
+            TestAccount::absent(),
+            TestAccount::program(spl_token::ID),
+            TestAccount::program(system_program::ID),
+        ], artist, buy_dutch::handler)
+    }
+}
+
+#[test]
+fn linear_decay_runs_from_the_starting_bid_to_the_floor() {
+    let auction = dutch(PriceDecay::Linear);
+
+    assert_eq!(auction.dutch_price(-60).unwrap(), 1_000);
+    assert_eq!(auction.dutch_price(0).unwrap(), 1_000);
+    assert_eq!(auction.dutch_price(1).unwrap(), 1_000);
+    assert_eq!(auction.dutch_price(1_800).unwrap(), 550);
+    assert_eq!(auction.dutch_price(2_700).unwrap(), 325);
+    assert_eq!(auction.dutch_price(3_600).unwrap(), 100);
+    assert_eq!(auction.dutch_price(7_200).unwrap(), 100);
+}
+
+#[test]
+fn stepwise_decay_drops_at_each_interval_and_stops_at_the_floor() {
+    let auction = dutch(PriceDecay::Stepwise { interval_seconds: 600, step_amount: 200 });
+
+    assert_eq!(auction.dutch_price(0).unwrap(), 1_000);
+    assert_eq!(auction.dutch_price(599).unwrap(), 1_000);
+    for (step, price) in [800, 600, 400, 200].into_iter().enumerate() {
+        let boundary = 600 * (step as i64 + 1);
+        assert_eq!(auction.dutch_price(boundary - 1).unwrap(), price + 200);
+        assert_eq!(auction.dutch_price(boundary).unwrap(), price);
+    }
+
+    // The fifth step would go below the floor
+    assert_eq!(auction.dutch_price(3_000).unwrap(), 100);
+    assert_eq!(auction.dutch_price(3_600).unwrap(), 100);
+    assert_eq!(auction.dutch_price(7_200).unwrap(), 100);
+}
+
+#[test]
+fn only_dutch_auctions_have_a_decaying_price() {
+    assert_eq!(auction(english()).dutch_price(0), Err(MarketplaceError::InvalidAuctionType.into()));
+}
+
+#[test]
+fn buy_dutch_charges_the_current_price() {
+    let sale = Sale::new();
+    let artist = infos([TestAccount::wallet(sale.artist)]);
+
+    let bought = sale.buy_dutch(0, 3_600, artist).unwrap();
+    assert_eq!(bought.auction.current_bid, PRICE);
+    assert_eq!(bought.buyer.lamports(), LAMPORTS - PRICE);
+    assert_eq!(token_amount(&bought.buyer_token_account.to_account_info()), 1);
+
+    // Halfway through, half of the way down to the floor
+    let artist = infos([TestAccount::wallet(sale.artist)]);
+    let bought = sale.buy_dutch(-1_800, 1_800, artist).unwrap();
+    let price = 3 * PRICE / 4;
+    assert_eq!(bought.auction.current_bid, price);
+    assert!(bought.auction.status == AuctionStatus::Ended);
+    assert_eq!(bought.buyer.lamports(), LAMPORTS - price);
+    assert_eq!(artist[0].lamports(), LAMPORTS + price / 10);
+}
+
+#[test]
+fn buy_dutch_is_closed_from_end_time() {
+    let sale = Sale::new();
+    let artist = infos([TestAccount::wallet(sale.artist)]);
+    let ended = Err(MarketplaceError::AuctionEnded.into());
+
+    assert_eq!(sale.buy_dutch(-3_600, 0, artist).map(|_| ()), ended);
+    assert_eq!(sale.buy_dutch(-3_600, -60, artist).map(|_| ()), ended);
+}
@@ -0,0 +1,133 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::spl_token;
+
+use marketplace_engine::errors::MarketplaceError;
+use marketplace_engine::instructions::*;
+use marketplace_engine::state::*;
+
+use common::*;
+
+fn english_auction(min_increment: BidIncrement) -> Auction {
+    Auction {
+        starting_bid: 1_000,
+        current_bid: 1_000,
+        price_cap: 10_000,
+        auction_type: AuctionType::English { min_increment, extension_window: 300, max_extension: 600 },
+        ..auction(english())
+    }
+}
+
+impl Sale {
+    /// Open the bidding on the sale's auction, ending at `end_time` with no bid on it yet
+    fn place_opening_bid(&self, end_time: i64) -> Result<PlaceBid<'static>> {
+        let auction = Auction { highest_bidder: None, end_time, ..self.auction_state(english()) };
+        run::<PlaceBid, 10>([
+            self.auction_account(&auction),
+            TestAccount::signer(self.buyer),
+            self.event(),
+            TestAccount::absent(),
+            TestAccount::absent(),
+            TestAccount::absent(),
+            TestAccount::absent(),
+            TestAccount::absent(),
+            TestAccount::program(spl_token::ID),
+            TestAccount::program(system_program::ID),
+        ], infos([]), |ctx| place_bid::handler(ctx, PRICE))
+    }
+}
+
+#[test]
+fn the_opening_bid_may_match_the_starting_bid() {
+    let auction = english_auction(BidIncrement::BasisPoints(500));
+    assert_eq!(auction.min_next_bid().unwrap(), 1_000);
+}
+
+#[test]
+fn a_basis_point_increment_rounds_down_but_is_never_zero() {
+    let mut auction = english_auction(BidIncrement::BasisPoints(500));
+    auction.highest_bidder = Some(Pubkey::new_unique());
+
+    auction.current_bid = 1_000;
+    assert_eq!(auction.min_next_bid().unwrap(), 1_050);
+    auction.current_bid = 1_999;
+    assert_eq!(auction.min_next_bid().unwrap(), 1_999 + 99);
+    auction.current_bid = 19;
+    assert_eq!(auction.min_next_bid().unwrap(), 20);
+
+    auction.current_bid = u64::MAX;
+    assert_eq!(auction.min_next_bid(), Err(MarketplaceError::ArithmeticOverflow.into()));
+}
+
+#[test]
+fn an_absolute_increment_is_added_as_is() {
+    let mut auction = english_auction(BidIncrement::Absolute(25));
+    auction.highest_bidder = Some(Pubkey::new_unique());
+    assert_eq!(auction.min_next_bid().unwrap(), 1_025);
+
+    auction.auction_type = AuctionType::English {
+        min_increment: BidIncrement::Absolute(0),
+        extension_window: 300,
+        max_extension: 600,
+    };
+    assert_eq!(auction.min_next_bid().unwrap(), 1_001);
+}
+
+#[test]
+fn only_english_auctions_take_open_bids() {
+    let auction = auction(AuctionType::Dutch { floor_price: 1, decay: PriceDecay::Linear });
+    assert_eq!(auction.min_next_bid(), Err(MarketplaceError::InvalidAuctionType.into()));
+}
+
+#[test]
+fn bids_only_extend_the_auction_inside_the_sniping_window() {
+    let mut auction = english_auction(BidIncrement::BasisPoints(500));
+
+    auction.apply_anti_sniping(0);
+    auction.apply_anti_sniping(3_300);
+    assert_eq!((auction.end_time, auction.extended_seconds), (3_600, 0));
+
+    // A second inside the window pushes the end out so a full window remains
+    auction.apply_anti_sniping(3_301);
+    assert_eq!((auction.end_time, auction.extended_seconds), (3_601, 1));
+    auction.apply_anti_sniping(3_500);
+    assert_eq!((auction.end_time, auction.extended_seconds), (3_800, 200));
+}
+
+#[test]
+fn extensions_stop_at_the_max_extension() {
+    let mut auction = english_auction(BidIncrement::BasisPoints(500));
+
+    auction.apply_anti_sniping(3_599);
+    assert_eq!((auction.end_time, auction.extended_seconds), (3_899, 299));
+    auction.apply_anti_sniping(3_898);
+    assert_eq!((auction.end_time, auction.extended_seconds), (4_198, 598));
+
+    // Only what is left of the max extension is granted, then nothing more
+    auction.apply_anti_sniping(4_197);
+    assert_eq!((auction.end_time, auction.extended_seconds), (4_200, 600));
+    auction.apply_anti_sniping(4_199);
+    assert_eq!((auction.end_time, auction.extended_seconds), (4_200, 600));
+}
+
+#[test]
+fn other_auctions_are_never_extended() {
+    let mut auction = auction(AuctionType::Dutch { floor_price: 1, decay: PriceDecay::Linear });
+    auction.apply_anti_sniping(3_599);
+    assert_eq!((auction.end_time, auction.extended_seconds), (3_600, 0));
+}
+
+#[test]
+fn place_bid_extends_an_auction_bid_on_inside_the_window() {
+    let sale = Sale::new();
+
+    let bid = sale.place_opening_bid(100).unwrap();
+    assert_eq!((bid.auction.end_time, bid.auction.extended_seconds), (300, 200));
+    assert_eq!(bid.auction.highest_bidder, Some(sale.buyer));
+    assert_eq!(bid.bidder.lamports(), LAMPORTS - PRICE);
+
+    let bid = sale.place_opening_bid(3_600).unwrap();
+    assert_eq!((bid.auction.end_time, bid.auction.extended_seconds), (3_600, 0));
+}
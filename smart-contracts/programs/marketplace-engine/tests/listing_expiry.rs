@@ -0,0 +1,70 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::spl_token;
+
+use marketplace_engine::errors::MarketplaceError;
+use marketplace_engine::instructions::*;
+use marketplace_engine::state::*;
+
+use common::*;
+
+impl Sale {
+    /// Crank `expire_listing` on the sale's listing, expiring at `expires_at`
+    fn expire_listing(&self, expires_at: Option<i64>, cranker: Pubkey) -> Result<ExpireListing<'static>> {
+        let listing = Listing { expires_at, ..self.listing_state() };
+        run::<ExpireListing, 6>([
+            self.listing_account(&listing).with_lamports(Rent::default().minimum_balance(Listing::LEN)),
+            TestAccount::wallet(self.seller),
+            self.ticket_account(Pubkey::new_unique(), self.seller, 0),
+            self.listing_escrow(1),
+            TestAccount::signer(cranker),
+            TestAccount::program(spl_token::ID),
+        ], infos([]), expire_listing::handler)
+    }
+}
+
+#[test]
+fn a_listing_cannot_be_expired_early() {
+    let sale = Sale::new();
+    let not_expired = Some(MarketplaceError::ListingNotExpired.into());
+
+    assert_eq!(sale.expire_listing(Some(1), Pubkey::new_unique()).err(), not_expired);
+    assert_eq!(sale.expire_listing(None, Pubkey::new_unique()).err(), not_expired);
+    assert!(sale.expire_listing(Some(0), Pubkey::new_unique()).is_ok());
+}
+
+#[test]
+fn the_cranker_earns_the_bounty_and_the_seller_the_rest() {
+    let sale = Sale::new();
+    let rent = Rent::default().minimum_balance(Listing::LEN);
+
+    let expired = sale.expire_listing(Some(-60), Pubkey::new_unique()).unwrap();
+
+    // Exactly the bounty, however much rent the listing held
+    assert_eq!(expired.cranker.lamports(), LAMPORTS + Listing::EXPIRY_BOUNTY);
+
+    // Plus the rent of the closed ticket escrow
+    assert_eq!(expired.seller.lamports(), LAMPORTS + rent - Listing::EXPIRY_BOUNTY + LAMPORTS);
+    assert_eq!(expired.listing.to_account_info().lamports(), 0);
+    assert_eq!(expired.escrow_token_account.to_account_info().lamports(), 0);
+}
+
+#[test]
+fn a_seller_expiring_their_own_listing_gets_all_the_rent() {
+    let sale = Sale::new();
+    let rent = Rent::default().minimum_balance(Listing::LEN);
+
+    let expired = sale.expire_listing(Some(-60), sale.seller).unwrap();
+    assert_eq!(expired.cranker.lamports(), LAMPORTS);
+    assert_eq!(expired.seller.lamports(), LAMPORTS + rent + LAMPORTS);
+}
+
+#[test]
+fn the_ticket_goes_back_to_the_seller() {
+    let sale = Sale::new();
+
+    let expired = sale.expire_listing(Some(-60), Pubkey::new_unique()).unwrap();
+    assert_eq!(token_amount(&expired.seller_token_account.to_account_info()), 1);
+    assert!(expired.listing.status == ListingStatus::Expired);
+}
@@ -0,0 +1,103 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::spl_token;
+
+use marketplace_engine::errors::MarketplaceError;
+use marketplace_engine::instructions::*;
+use marketplace_engine::state::*;
+
+use common::*;
+
+impl Sale {
+    fn make_offer(&self, listing: &Listing) -> Result<MakeOffer<'static>> {
+        run::<MakeOffer, 9>([
+            TestAccount::uncreated(self.offer_key()),
+            self.listing_account(listing),
+            TestAccount::signer(self.buyer),
+            self.event(),
+            TestAccount::absent(),
+            TestAccount::absent(),
+            TestAccount::absent(),
+            TestAccount::program(spl_token::ID),
+            TestAccount::program(system_program::ID),
+        ], infos([]), |ctx| make_offer::handler(ctx, PRICE, i64::MAX))
+    }
+
+    fn accept_offer(&self, listing: &Listing, artist: &'static [AccountInfo<'static>]) -> Result<AcceptOffer<'static>> {
+        run::<AcceptOffer, 13>([
+            self.offer(OfferStatus::Active),
+            self.listing_account(listing),
+            TestAccount::signer(self.seller),
+            TestAccount::wallet(self.buyer),
+            self.buyer_ticket_account(),
+            self.listing_escrow(1),
+            self.event(),
+            self.royalty_config(self.event),
+            self.royalty_stats(self.event),
+            TestAccount::absent(),
+            TestAccount::absent(),
+            TestAccount::absent(),
+            TestAccount::program(spl_token::ID),
+        ], artist, accept_offer::handler)
+    }
+}
+
+#[test]
+fn offers_are_only_escrowed_against_unexpired_listings() {
+    let sale = Sale::new();
+    let expired = Listing { expires_at: Some(0), ..sale.listing_state() };
+    assert_eq!(sale.make_offer(&expired).err(), Some(MarketplaceError::ListingExpired.into()));
+
+    let made = sale.make_offer(&sale.listing_state()).unwrap();
+    let rent = Rent::default().minimum_balance(Offer::LEN);
+    assert_eq!(made.offer.to_account_info().lamports(), rent + PRICE);
+    assert_eq!(made.buyer.lamports(), LAMPORTS - rent - PRICE);
+    assert_eq!(made.offer.amount, PRICE);
+}
+
+#[test]
+fn an_offer_cannot_settle_against_an_expired_listing() {
+    let sale = Sale::new();
+    let expired = Listing { expires_at: Some(0), ..sale.listing_state() };
+    let artist = infos([TestAccount::wallet(sale.artist)]);
+    assert_eq!(sale.accept_offer(&expired, artist).err(), Some(MarketplaceError::ListingExpired.into()));
+
+    let accepted = sale.accept_offer(&sale.listing_state(), artist).unwrap();
+    assert_eq!(token_amount(&accepted.buyer_token_account.to_account_info()), 1);
+    assert_eq!(artist[0].lamports(), LAMPORTS + PRICE / 10);
+    // The seller is paid, and gets back the rent of the closed listing and its ticket escrow
+    assert_eq!(accepted.seller.lamports(), 3 * LAMPORTS + PRICE - PRICE / 10);
+}
+
+#[test]
+fn an_accepted_offer_pays_out_exactly_the_escrowed_amount() {
+    let config = royalty_config(Pubkey::new_unique(), recipients(&[333, 167]));
+
+    for escrowed in [1, 999, 1_001, 123_457] {
+        let split = config.split(escrowed).unwrap();
+        let royalties: u64 = split.royalties.iter().map(|payout| payout.amount).sum();
+
+        assert_eq!(royalties + split.seller_amount, escrowed);
+        assert_eq!(royalties, escrowed * 500 / 10_000);
+    }
+}
+
+#[test]
+fn accepting_a_counter_tops_the_escrow_up_to_the_counter_price() {
+    let countered = offer(600, 850, OfferStatus::CounterOffered);
+    assert_eq!(countered.counter_top_up().unwrap(), 250);
+
+    let split = royalty_config(countered.event, recipients(&[333, 167]))
+        .split(countered.amount + 250)
+        .unwrap();
+    let royalties: u64 = split.royalties.iter().map(|payout| payout.amount).sum();
+    assert_eq!(royalties + split.seller_amount, 850);
+}
+
+#[test]
+fn there_is_no_top_up_without_a_standing_counter() {
+    assert!(offer(600, 0, OfferStatus::Active).counter_top_up().is_err());
+    assert!(offer(600, 850, OfferStatus::Cancelled).counter_top_up().is_err());
+}
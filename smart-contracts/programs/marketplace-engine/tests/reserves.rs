@@ -0,0 +1,161 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::spl_token;
+
+use marketplace_engine::errors::MarketplaceError;
+use marketplace_engine::instructions::*;
+use marketplace_engine::state::*;
+
+use common::*;
+
+const SALT: [u8; 32] = [7; 32];
+
+impl Sale {
+    /// The buyer's bid of `PRICE` on top of an English auction that ended at `end_time`,
+    /// behind a hidden reserve of `reserve_price`
+    fn reserve_auction(&self, reserve_price: u64, end_time: i64) -> Auction {
+        Auction {
+            reserve_hash: Some(Auction::reserve_commitment(reserve_price, &SALT)),
+            end_time,
+            ..self.auction_state(english())
+        }
+    }
+
+    /// Settle `auction`, handing over a token account of `ticket_to` for the ticket
+    fn settle_auction(
+        &self,
+        auction: &Auction,
+        artist: &'static [AccountInfo<'static>],
+        ticket_to: Pubkey,
+        reveal: Option<ReserveReveal>,
+    ) -> Result<SettleAuction<'static>> {
+        run::<SettleAuction, 15>([
+            self.auction_account(auction),
+            TestAccount::wallet(self.seller),
+            TestAccount::signer(Pubkey::new_unique()),
+            self.ticket_account(Pubkey::new_unique(), ticket_to, 0),
+            self.auction_escrow(1),
+            self.event(),
+            self.royalty_config(self.event),
+            self.royalty_stats(self.event),
+            TestAccount::absent(),
+            TestAccount::absent(),
+            TestAccount::uncreated(BidRefund::address(&self.auction_key(), &self.buyer, 1)),
+            TestAccount::absent(),
+            TestAccount::absent(),
+            TestAccount::program(spl_token::ID),
+            TestAccount::program(system_program::ID),
+        ], artist, |ctx| settle_auction::handler(ctx, reveal))
+    }
+
+    fn cancel_auction(&self, auction: &Auction) -> Result<CancelAuction<'static>> {
+        run::<CancelAuction, 6>([
+            self.auction_account(auction),
+            TestAccount::signer(self.seller),
+            self.ticket_account(Pubkey::new_unique(), self.seller, 0),
+            self.auction_escrow(1),
+            TestAccount::absent(),
+            TestAccount::program(spl_token::ID),
+        ], infos([]), cancel_auction::handler)
+    }
+}
+
+fn reveal(reserve_price: u64, salt: [u8; 32]) -> Option<ReserveReveal> {
+    Some(ReserveReveal { reserve_price, salt })
+}
+
+#[test]
+fn a_reserve_commitment_binds_both_the_price_and_the_salt() {
+    let commitment = Auction::reserve_commitment(PRICE, &SALT);
+    assert_ne!(commitment, Auction::reserve_commitment(PRICE + 1, &SALT));
+    assert_ne!(commitment, Auction::reserve_commitment(PRICE, &[8; 32]));
+    assert_eq!(commitment, Auction::reserve_commitment(PRICE, &SALT));
+}
+
+#[test]
+fn a_reveal_with_the_wrong_salt_is_rejected() {
+    let sale = Sale::new();
+    let auction = sale.reserve_auction(PRICE / 2, 0);
+    let artist = infos([TestAccount::wallet(sale.artist)]);
+
+    assert_eq!(
+        sale.settle_auction(&auction, artist, sale.buyer, reveal(PRICE / 2, [8; 32])).err(),
+        Some(MarketplaceError::InvalidReserveReveal.into())
+    );
+    assert_eq!(
+        sale.settle_auction(&auction, artist, sale.buyer, reveal(PRICE / 2 + 1, SALT)).err(),
+        Some(MarketplaceError::InvalidReserveReveal.into())
+    );
+}
+
+#[test]
+fn a_met_reserve_sells_to_the_top_bidder() {
+    let sale = Sale::new();
+    let artist = infos([TestAccount::wallet(sale.artist)]);
+
+    let settled = sale.settle_auction(&sale.reserve_auction(PRICE, 0), artist, sale.buyer, reveal(PRICE, SALT)).unwrap();
+    assert_eq!(token_amount(&settled.recipient_token_account.to_account_info()), 1);
+    assert_eq!(artist[0].lamports(), LAMPORTS + PRICE / 10);
+    assert_eq!(settled.highest_bidder_refund.unwrap().amount, 0);
+}
+
+#[test]
+fn an_unmet_reserve_returns_the_ticket_and_credits_the_top_bid_back() {
+    let sale = Sale::new();
+    let artist = infos([TestAccount::wallet(sale.artist)]);
+    let auction = sale.reserve_auction(PRICE + 1, 0);
+
+    // The ticket can't go to the top bidder
+    assert_eq!(
+        sale.settle_auction(&auction, artist, sale.buyer, reveal(PRICE + 1, SALT)).err(),
+        Some(MarketplaceError::Unauthorized.into())
+    );
+
+    let settled = sale.settle_auction(&auction, artist, sale.seller, reveal(PRICE + 1, SALT)).unwrap();
+    assert_eq!(token_amount(&settled.recipient_token_account.to_account_info()), 1);
+    assert_eq!(artist[0].lamports(), LAMPORTS);
+
+    let refund = settled.highest_bidder_refund.unwrap();
+    let rent = Rent::default().minimum_balance(BidRefund::LEN);
+    assert_eq!(refund.bidder, sale.buyer);
+    assert_eq!(refund.amount, PRICE);
+    assert_eq!(refund.rent_payer, settled.cranker.key());
+    assert_eq!(refund.to_account_info().lamports(), rent + PRICE);
+}
+
+#[test]
+fn the_sale_stands_once_the_reveal_period_lapses() {
+    let sale = Sale::new();
+    let artist = infos([TestAccount::wallet(sale.artist)]);
+
+    // Unrevealed, settlement waits for the seller until the period is over
+    let pending = sale.reserve_auction(PRICE + 1, 1 - Auction::RESERVE_REVEAL_PERIOD);
+    assert_eq!(
+        sale.settle_auction(&pending, artist, sale.buyer, None).err(),
+        Some(MarketplaceError::ReserveRevealPending.into())
+    );
+
+    let lapsed = sale.reserve_auction(PRICE + 1, -Auction::RESERVE_REVEAL_PERIOD);
+    let settled = sale.settle_auction(&lapsed, artist, sale.buyer, None).unwrap();
+    assert_eq!(token_amount(&settled.recipient_token_account.to_account_info()), 1);
+    assert_eq!(artist[0].lamports(), LAMPORTS + PRICE / 10);
+}
+
+#[test]
+fn an_auction_cannot_be_cancelled_after_the_first_bid() {
+    let sale = Sale::new();
+    let bid_on = Auction { end_time: 3_600, ..sale.auction_state(english()) };
+    assert_eq!(sale.cancel_auction(&bid_on).err(), Some(MarketplaceError::AuctionHasBids.into()));
+
+    let sealed = Auction {
+        highest_bidder: None,
+        sealed_bid_count: 1,
+        ..sale.auction_state(AuctionType::Sealed { pricing: SealedPricing::FirstPrice, reveal_duration: 600 })
+    };
+    assert_eq!(sale.cancel_auction(&sealed).err(), Some(MarketplaceError::AuctionHasBids.into()));
+
+    let cancelled = sale.cancel_auction(&Auction { highest_bidder: None, ..bid_on }).unwrap();
+    assert_eq!(token_amount(&cancelled.seller_token_account.to_account_info()), 1);
+}
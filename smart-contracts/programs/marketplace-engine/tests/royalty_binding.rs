@@ -0,0 +1,298 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::spl_token;
+
+use marketplace_engine::errors::MarketplaceError;
+use marketplace_engine::instructions::*;
+use marketplace_engine::state::*;
+
+use common::*;
+
+impl Sale {
+    fn create_listing(&self, config_event: Pubkey) -> Result<()> {
+        let (counter, bump) = pda(&[b"listing_counter", self.ticket_mint.as_ref()]);
+        accounts::<CreateListing, 12>([
+            TestAccount::uncreated(self.listing_key()),
+            TestAccount::signer(self.seller),
+            self.ticket_mint(),
+            self.ticket_account(Pubkey::new_unique(), self.seller, 1),
+            self.listing_escrow(0),
+            self.ticket_data(),
+            self.event(),
+            self.royalty_config(config_event),
+            TestAccount::program_account(counter, &ListingCounter { ticket_mint: self.ticket_mint, listings: 1, bump }),
+            TestAccount::program(spl_token::ID),
+            TestAccount::program(system_program::ID),
+            TestAccount::rent(),
+        ])
+        .map(|_| ())
+    }
+
+    fn create_auction(&self, config_event: Pubkey) -> Result<()> {
+        let (counter, bump) = pda(&[b"auction_counter", self.ticket_mint.as_ref()]);
+        accounts::<CreateAuction, 14>([
+            TestAccount::uncreated(self.auction_key()),
+            TestAccount::signer(self.seller),
+            self.ticket_mint(),
+            self.ticket_account(Pubkey::new_unique(), self.seller, 1),
+            self.auction_escrow(0),
+            self.ticket_data(),
+            self.event(),
+            self.royalty_config(config_event),
+            TestAccount::absent(),
+            TestAccount::absent(),
+            TestAccount::program_account(counter, &AuctionCounter { ticket_mint: self.ticket_mint, auctions: 1, bump }),
+            TestAccount::program(spl_token::ID),
+            TestAccount::program(system_program::ID),
+            TestAccount::rent(),
+        ])
+        .map(|_| ())
+    }
+
+    fn buy_ticket(&self, config_event: Pubkey) -> Result<()> {
+        accounts::<BuyTicket, 13>([
+            self.listing(),
+            TestAccount::signer(self.buyer),
+            TestAccount::wallet(self.seller),
+            self.buyer_ticket_account(),
+            self.listing_escrow(1),
+            self.event(),
+            self.royalty_config(config_event),
+            self.royalty_stats(self.event),
+            TestAccount::absent(),
+            TestAccount::absent(),
+            TestAccount::absent(),
+            TestAccount::program(spl_token::ID),
+            TestAccount::program(system_program::ID),
+        ])
+        .map(|_| ())
+    }
+
+    fn accept_offer(&self, config_event: Pubkey) -> Result<()> {
+        accounts::<AcceptOffer, 13>([
+            self.offer(OfferStatus::Active),
+            self.listing(),
+            TestAccount::signer(self.seller),
+            TestAccount::wallet(self.buyer),
+            self.buyer_ticket_account(),
+            self.listing_escrow(1),
+            self.event(),
+            self.royalty_config(config_event),
+            self.royalty_stats(self.event),
+            TestAccount::absent(),
+            TestAccount::absent(),
+            TestAccount::absent(),
+            TestAccount::program(spl_token::ID),
+        ])
+        .map(|_| ())
+    }
+
+    fn accept_counter_offer(&self, config_event: Pubkey) -> Result<()> {
+        accounts::<AcceptCounterOffer, 15>([
+            self.offer(OfferStatus::CounterOffered),
+            self.listing(),
+            TestAccount::signer(self.buyer),
+            TestAccount::wallet(self.seller),
+            self.buyer_ticket_account(),
+            self.listing_escrow(1),
+            self.event(),
+            self.royalty_config(config_event),
+            self.royalty_stats(self.event),
+            TestAccount::absent(),
+            TestAccount::absent(),
+            TestAccount::absent(),
+            TestAccount::absent(),
+            TestAccount::program(spl_token::ID),
+            TestAccount::program(system_program::ID),
+        ])
+        .map(|_| ())
+    }
+
+    fn settle_auction(&self, config_event: Pubkey) -> Result<()> {
+        let english = AuctionType::English {
+            min_increment: BidIncrement::Absolute(1),
+            extension_window: 0,
+            max_extension: 0,
+        };
+        accounts::<SettleAuction, 15>([
+            self.auction(english),
+            TestAccount::wallet(self.seller),
+            TestAccount::signer(self.buyer),
+            self.buyer_ticket_account(),
+            self.auction_escrow(1),
+            self.event(),
+            self.royalty_config(config_event),
+            self.royalty_stats(self.event),
+            TestAccount::absent(),
+            TestAccount::absent(),
+            TestAccount::absent(),
+            TestAccount::absent(),
+            TestAccount::absent(),
+            TestAccount::program(spl_token::ID),
+            TestAccount::program(system_program::ID),
+        ])
+        .map(|_| ())
+    }
+
+    fn buy_dutch(&self, config_event: Pubkey) -> Result<()> {
+        accounts::<BuyDutch, 14>([
+            self.auction(AuctionType::Dutch { floor_price: 1, decay: PriceDecay::Linear }),
+            TestAccount::signer(self.buyer),
+            TestAccount::wallet(self.seller),
+            self.buyer_ticket_account(),
+            self.auction_escrow(1),
+            self.event(),
+            self.royalty_config(config_event),
+            self.royalty_stats(self.event),
+            TestAccount::absent(),
+            TestAccount::absent(),
+            TestAccount::absent(),
+            TestAccount::absent(),
+            TestAccount::program(spl_token::ID),
+            TestAccount::program(system_program::ID),
+        ])
+        .map(|_| ())
+    }
+
+    /// Runs `buy_many` on a one-listing cart. The cart carries another event's RoyaltyStats, so
+    /// a config that gets past the binding stops at the very next check instead of paying out.
+    fn buy_many(&self, config_event: Pubkey) -> Result<()> {
+        let cart = infos([
+            self.listing(),
+            TestAccount::wallet(self.seller),
+            self.listing_escrow(1),
+            self.buyer_ticket_account(),
+            self.event(),
+            self.royalty_config(config_event),
+            self.royalty_stats(Pubkey::new_unique()),
+            TestAccount::wallet(Pubkey::new_unique()),
+        ]);
+        run::<BuyMany, 5>([
+            TestAccount::signer(self.buyer),
+            TestAccount::absent(),
+            TestAccount::absent(),
+            TestAccount::program(spl_token::ID),
+            TestAccount::program(system_program::ID),
+        ], cart, |ctx| buy_many::handler(ctx, 1, false))
+        .map(|_| ())
+    }
+
+    /// Runs `buy_bundle` on a one-ticket bundle, stopped short of paying out the same way
+    fn buy_bundle(&self, config_event: Pubkey) -> Result<()> {
+        let bundle_id: u64 = 7;
+        let (bundle, bump) = pda(&[b"bundle", self.seller.as_ref(), &bundle_id.to_le_bytes()]);
+        let bundle_escrow = anchor_spl::associated_token::get_associated_token_address(&bundle, &self.ticket_mint);
+        let remaining = infos([
+            self.ticket_account(bundle_escrow, bundle, 1),
+            self.buyer_ticket_account(),
+            self.event(),
+            self.royalty_config(config_event),
+            self.royalty_stats(Pubkey::new_unique()),
+            TestAccount::wallet(Pubkey::new_unique()),
+        ]);
+        run::<BuyBundle, 8>([
+            TestAccount::program_account(bundle, &BundleListing {
+                seller: self.seller,
+                bundle_id,
+                tickets: vec![BundleTicket { ticket_mint: self.ticket_mint, event: self.event, face_value: 100 }],
+                price: 150,
+                payment_mint: None,
+                price_cap: 200,
+                expires_at: None,
+                created_at: 0,
+                status: ListingStatus::Active,
+                bump,
+            }),
+            TestAccount::signer(self.buyer),
+            TestAccount::wallet(self.seller),
+            TestAccount::absent(),
+            TestAccount::absent(),
+            TestAccount::absent(),
+            TestAccount::program(spl_token::ID),
+            TestAccount::program(system_program::ID),
+        ], remaining, buy_bundle::handler)
+        .map(|_| ())
+    }
+}
+
+fn mismatch() -> Result<()> {
+    Err(MarketplaceError::RoyaltyConfigMismatch.into())
+}
+
+#[test]
+fn create_listing_uses_the_tickets_events_royalties() {
+    let sale = Sale::new();
+    assert_eq!(sale.create_listing(sale.event), Ok(()));
+    assert_eq!(sale.create_listing(Pubkey::new_unique()), mismatch());
+}
+
+#[test]
+fn create_auction_uses_the_tickets_events_royalties() {
+    let sale = Sale::new();
+    assert_eq!(sale.create_auction(sale.event), Ok(()));
+    assert_eq!(sale.create_auction(Pubkey::new_unique()), mismatch());
+}
+
+#[test]
+fn buy_ticket_uses_the_listed_events_royalties() {
+    let sale = Sale::new();
+    assert_eq!(sale.buy_ticket(sale.event), Ok(()));
+    assert_eq!(sale.buy_ticket(Pubkey::new_unique()), mismatch());
+}
+
+#[test]
+fn accept_offer_uses_the_listed_events_royalties() {
+    let sale = Sale::new();
+    assert_eq!(sale.accept_offer(sale.event), Ok(()));
+    assert_eq!(sale.accept_offer(Pubkey::new_unique()), mismatch());
+}
+
+#[test]
+fn accept_counter_offer_uses_the_listed_events_royalties() {
+    let sale = Sale::new();
+    assert_eq!(sale.accept_counter_offer(sale.event), Ok(()));
+    assert_eq!(sale.accept_counter_offer(Pubkey::new_unique()), mismatch());
+}
+
+#[test]
+fn settle_auction_uses_the_auctioned_events_royalties() {
+    let sale = Sale::new();
+    assert_eq!(sale.settle_auction(sale.event), Ok(()));
+    assert_eq!(sale.settle_auction(Pubkey::new_unique()), mismatch());
+}
+
+#[test]
+fn buy_dutch_uses_the_auctioned_events_royalties() {
+    let sale = Sale::new();
+    assert_eq!(sale.buy_dutch(sale.event), Ok(()));
+    assert_eq!(sale.buy_dutch(Pubkey::new_unique()), mismatch());
+}
+
+#[test]
+fn buy_many_uses_each_listed_events_royalties() {
+    let sale = Sale::new();
+    assert_eq!(sale.buy_many(sale.event), Err(MarketplaceError::CartAccountMismatch.into()));
+    assert_eq!(sale.buy_many(Pubkey::new_unique()), mismatch());
+}
+
+#[test]
+fn buy_bundle_uses_each_bundled_events_royalties() {
+    let sale = Sale::new();
+    assert_eq!(sale.buy_bundle(sale.event), Err(MarketplaceError::BundleAccountMismatch.into()));
+    assert_eq!(sale.buy_bundle(Pubkey::new_unique()), mismatch());
+}
+
+#[test]
+fn settlements_halt_once_the_event_is_cancelled() {
+    let sale = Sale { event_cancelled: true, ..Sale::new() };
+    let cancelled = Err(MarketplaceError::EventCancelled.into());
+    assert_eq!(sale.buy_ticket(sale.event), cancelled);
+    assert_eq!(sale.accept_offer(sale.event), cancelled);
+    assert_eq!(sale.accept_counter_offer(sale.event), cancelled);
+    assert_eq!(sale.settle_auction(sale.event), cancelled);
+    assert_eq!(sale.buy_dutch(sale.event), cancelled);
+    assert_eq!(sale.buy_many(sale.event), cancelled);
+    assert_eq!(sale.buy_bundle(sale.event), cancelled);
+}
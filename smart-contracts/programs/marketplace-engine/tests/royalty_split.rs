@@ -0,0 +1,55 @@
+mod common;
+
+use anchor_lang::prelude::*;
+
+use marketplace_engine::state::*;
+
+use common::*;
+
+fn amounts(split: &RoyaltySplit) -> Vec<u64> {
+    split.royalties.iter().map(|payout| payout.amount).collect()
+}
+
+#[test]
+fn rounding_dust_goes_to_the_dust_recipient() {
+    // 5% of 1_001 is 50, but 3.33% and 1.67% round down to 33 and 16
+    let mut config = royalty_config(Pubkey::new_unique(), recipients(&[333, 167]));
+
+    let split = config.split(1_001).unwrap();
+    assert_eq!(amounts(&split), vec![34, 16]);
+    assert_eq!(split.seller_amount, 951);
+
+    config.dust_recipient = 1;
+    let split = config.split(1_001).unwrap();
+    assert_eq!(amounts(&split), vec![33, 17]);
+    assert_eq!(split.seller_amount, 951);
+}
+
+#[test]
+fn payouts_go_to_each_recipients_wallet_in_order() {
+    let config = royalty_config(Pubkey::new_unique(), recipients(&[100, 200, 300]));
+    let split = config.split(10_000).unwrap();
+
+    assert!(split.royalties.iter().map(|payout| payout.wallet).eq(config.recipients.iter().map(|r| r.wallet)));
+    assert_eq!(amounts(&split), vec![100, 200, 300]);
+    assert_eq!(split.seller_amount, 9_400);
+}
+
+#[test]
+fn the_split_always_adds_up_to_the_price() {
+    // Dust to the first and to the last of seven recipients of 10% each
+    for dust_recipient in [0, 6] {
+        let config = RoyaltyConfig {
+            dust_recipient,
+            ..royalty_config(Pubkey::new_unique(), recipients(&[1_000; 7]))
+        };
+
+        for price in [0, 1, 9, 10, 99, 1_001, 123_457, u64::MAX] {
+            let split = config.split(price).unwrap();
+            let royalties: u128 = amounts(&split).iter().map(|&amount| amount as u128).sum();
+
+            assert_eq!(royalties + split.seller_amount as u128, price as u128);
+            assert_eq!(royalties, price as u128 * 7_000 / 10_000);
+        }
+    }
+}
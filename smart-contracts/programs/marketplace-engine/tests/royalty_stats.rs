@@ -0,0 +1,54 @@
+mod common;
+
+use anchor_lang::prelude::*;
+
+use marketplace_engine::state::*;
+
+use common::*;
+
+#[test]
+fn record_sale_updates_the_event_totals() {
+    let event = Pubkey::new_unique();
+    let artist = Pubkey::new_unique();
+    let venue = Pubkey::new_unique();
+    let config = royalty_config(event, vec![
+        RoyaltyRecipient { wallet: artist, bps: 500 },
+        RoyaltyRecipient { wallet: venue, bps: 250 },
+    ]);
+    let mut stats = royalty_stats(event);
+
+    for price in [1_000, 4_000, 2_500] {
+        stats.record_sale(price, &config.split(price).unwrap()).unwrap();
+    }
+
+    let analytics = stats.analytics();
+    assert_eq!(analytics.event, event);
+    assert_eq!(analytics.total_volume, 7_500);
+    assert_eq!(analytics.sale_count, 3);
+    assert_eq!(analytics.highest_price, 4_000);
+    assert_eq!(analytics.average_price, 2_500);
+    assert_eq!(analytics.total_royalties, 50 + 25 + 200 + 100 + 125 + 62);
+    assert!(analytics.payees == vec![
+        RoyaltyPayout { wallet: artist, amount: 50 + 200 + 125 },
+        RoyaltyPayout { wallet: venue, amount: 25 + 100 + 62 },
+    ]);
+}
+
+#[test]
+fn record_sale_accounts_for_every_payout_across_wallet_rotations() {
+    let event = Pubkey::new_unique();
+    let mut stats = royalty_stats(event);
+
+    // Far more distinct wallets than there are payee entries
+    for _ in 0..3 * RoyaltyStats::MAX_PAYEES {
+        let config = royalty_config(event, recipients(&[100; RoyaltyTerms::MAX_RECIPIENTS]));
+        stats.record_sale(10_000, &config.split(10_000).unwrap()).unwrap();
+
+        assert!(stats.payees.len() <= RoyaltyStats::MAX_PAYEES);
+        assert!(stats.payees.iter().zip(&config.recipients).all(|(payee, recipient)| payee.wallet == recipient.wallet));
+    }
+
+    let paid: u64 = stats.payees.iter().map(|payee| payee.amount).sum();
+    assert_eq!(paid, stats.total_royalties);
+    assert_eq!(stats.total_royalties, (3 * RoyaltyStats::MAX_PAYEES * RoyaltyTerms::MAX_RECIPIENTS * 100) as u64);
+}
@@ -0,0 +1,70 @@
+mod common;
+
+use marketplace_engine::errors::MarketplaceError;
+use marketplace_engine::state::*;
+
+use common::*;
+
+fn terms(price_cap_multiplier: u16) -> RoyaltyTerms {
+    RoyaltyTerms {
+        recipients: recipients(&[500, 250]),
+        dust_recipient: 0,
+        price_cap_multiplier,
+    }
+}
+
+#[test]
+fn price_cap_must_allow_at_least_face_value() {
+    let invalid = Err(MarketplaceError::InvalidPriceCap.into());
+    assert_eq!(terms(0).validate(), invalid);
+    assert_eq!(terms(9_999).validate(), invalid);
+
+    assert_eq!(terms(10_000).validate(), Ok(()));
+    assert_eq!(terms(u16::MAX).validate(), Ok(()));
+}
+
+#[test]
+fn royalties_cannot_exceed_the_sale_price() {
+    let mut terms = terms(20_000);
+    terms.recipients[0].bps = 9_751;
+    assert_eq!(terms.validate(), Err(MarketplaceError::InvalidRoyaltyConfig.into()));
+
+    terms.recipients[0].bps = 9_750;
+    assert_eq!(terms.validate(), Ok(()));
+}
+
+#[test]
+fn there_are_one_to_max_recipients() {
+    let invalid = Err(MarketplaceError::InvalidRoyaltyConfig.into());
+    let with = |count: usize| RoyaltyTerms { recipients: recipients(&vec![100; count]), ..terms(20_000) };
+
+    assert_eq!(with(0).validate(), invalid);
+    assert_eq!(with(1).validate(), Ok(()));
+    assert_eq!(with(RoyaltyTerms::MAX_RECIPIENTS).validate(), Ok(()));
+    assert_eq!(with(RoyaltyTerms::MAX_RECIPIENTS + 1).validate(), invalid);
+}
+
+#[test]
+fn basis_points_may_add_up_to_the_whole_price_but_no_more() {
+    let invalid = Err(MarketplaceError::InvalidRoyaltyConfig.into());
+    let with = |bps: &[u16]| RoyaltyTerms { recipients: recipients(bps), ..terms(20_000) };
+
+    assert_eq!(with(&[10_000]).validate(), Ok(()));
+    assert_eq!(with(&[5_000, 5_000]).validate(), Ok(()));
+    assert_eq!(with(&[1_000; RoyaltyTerms::MAX_RECIPIENTS]).validate(), Ok(()));
+    assert_eq!(with(&[10_001]).validate(), invalid);
+    assert_eq!(with(&[5_000, 5_001]).validate(), invalid);
+
+    // Summed wide enough that a full list of maximal entries can't wrap around
+    assert_eq!(with(&[u16::MAX; RoyaltyTerms::MAX_RECIPIENTS]).validate(), invalid);
+}
+
+#[test]
+fn the_dust_recipient_must_be_one_of_the_recipients() {
+    let mut terms = terms(20_000);
+    terms.dust_recipient = 1;
+    assert_eq!(terms.validate(), Ok(()));
+
+    terms.dust_recipient = 2;
+    assert_eq!(terms.validate(), Err(MarketplaceError::InvalidRoyaltyConfig.into()));
+}
@@ -0,0 +1,91 @@
+mod common;
+
+use anchor_lang::prelude::*;
+
+use marketplace_engine::state::*;
+
+use common::*;
+
+fn sealed_auction(nonce: u64, end_time: i64, pricing: SealedPricing) -> Auction {
+    Auction {
+        starting_bid: 100,
+        current_bid: 100,
+        price_cap: 10_000,
+        start_time: end_time - 3600,
+        end_time,
+        nonce,
+        ..auction(AuctionType::Sealed { pricing, reveal_duration: 600 })
+    }
+}
+
+fn sealed_bid(auction_key: Pubkey, auction: &Auction) -> SealedBid {
+    SealedBid {
+        auction: auction_key,
+        auction_nonce: auction.nonce,
+        bidder: Pubkey::new_unique(),
+        event: auction.event,
+        commitment: SealedBid::commitment(500, &[7; 32]),
+        deposit: 500,
+        payment_mint: None,
+        amount: 0,
+        revealed: false,
+        reveal_end: auction.settlement_time(),
+        bump: 255,
+    }
+}
+
+fn sealed_bid_address(auction_key: &Pubkey, bidder: &Pubkey, nonce: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"sealed_bid", auction_key.as_ref(), bidder.as_ref(), &nonce.to_le_bytes()],
+        &marketplace_engine::ID,
+    ).0
+}
+
+#[test]
+fn a_bid_from_an_earlier_auction_of_the_ticket_is_stale() {
+    let auction_key = Pubkey::new_unique();
+    let first = sealed_auction(1, 10_000, SealedPricing::FirstPrice);
+    let leftover = sealed_bid(auction_key, &first);
+    assert!(leftover.is_for(&auction_key, &first));
+
+    // Same ticket auctioned again at the same address, with its reveal window lined up
+    let mut second = sealed_auction(2, 50_000, SealedPricing::FirstPrice);
+    assert!(!leftover.is_for(&auction_key, &second));
+    second.end_time = first.end_time;
+    assert!(!leftover.is_for(&auction_key, &second));
+
+    // And the bidder gets a fresh sealed bid address to commit to it
+    assert_ne!(
+        sealed_bid_address(&auction_key, &leftover.bidder, first.nonce),
+        sealed_bid_address(&auction_key, &leftover.bidder, second.nonce)
+    );
+}
+
+#[test]
+fn a_bid_is_only_for_the_auction_it_was_committed_to() {
+    let auction_key = Pubkey::new_unique();
+    let auction = sealed_auction(1, 10_000, SealedPricing::FirstPrice);
+    let bid = sealed_bid(auction_key, &auction);
+
+    assert!(!bid.is_for(&Pubkey::new_unique(), &auction));
+}
+
+#[test]
+fn second_price_clears_at_the_runner_up_within_bounds() {
+    let mut auction = sealed_auction(1, 10_000, SealedPricing::SecondPrice);
+    auction.highest_bidder = Some(Pubkey::new_unique());
+    auction.current_bid = 900;
+    auction.second_bid = 400;
+
+    assert_eq!(auction.clearing_price(None), 400);
+    // Never below a revealed reserve, never above the winning bid
+    assert_eq!(auction.clearing_price(Some(600)), 600);
+    assert_eq!(auction.clearing_price(Some(2_000)), 900);
+
+    // A lone bidder pays the starting bid
+    auction.second_bid = 0;
+    assert_eq!(auction.clearing_price(None), 100);
+
+    auction.auction_type = AuctionType::Sealed { pricing: SealedPricing::FirstPrice, reveal_duration: 600 };
+    assert_eq!(auction.clearing_price(None), 900);
+}
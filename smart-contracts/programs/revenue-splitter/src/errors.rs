@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum SplitterError {
+    #[msg("Unauthorized operation")]
+    Unauthorized,
+
+    #[msg("Shares must be 1-10 distinct wallets totalling exactly 10000 bps")]
+    InvalidShares,
+
+    #[msg("Signer is not a shareholder of this splitter")]
+    NotAShareholder,
+
+    #[msg("Nothing to claim")]
+    NothingToClaim,
+
+    #[msg("Vesting tranches must be at most 4 entries totalling exactly 10000 bps")]
+    InvalidSchedule,
+
+    #[msg("Splitter has already been settled or cancelled")]
+    SplitterNotActive,
+
+    #[msg("Payouts are held back because the event was cancelled")]
+    PayoutsHeldBack,
+
+    #[msg("Event has not been cancelled")]
+    EventNotCancelled,
+
+    #[msg("Refunds are only open for cancelled events")]
+    RefundsNotOpen,
+
+    #[msg("Ticket does not belong to this splitter's event")]
+    TicketEventMismatch,
+
+    #[msg("Refund reserve cannot cover this ticket")]
+    InsufficientRefundReserve,
+
+    #[msg("Deposit amount must be greater than zero")]
+    InvalidAmount,
+
+    #[msg("Payment mint does not match the splitter")]
+    PaymentMintMismatch,
+
+    #[msg("Missing or invalid payment token account")]
+    InvalidPaymentAccount,
+
+    #[msg("Calculation overflow")]
+    CalculationOverflow,
+
+    #[msg("Event has not ended yet")]
+    EventNotOver,
+}
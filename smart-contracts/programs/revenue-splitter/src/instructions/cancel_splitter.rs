@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+use ticket_nft_core::state::Event;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct CancelSplitter<'info> {
+    #[account(
+        mut,
+        seeds = [b"splitter", splitter.event.as_ref()],
+        bump = splitter.bump
+    )]
+    pub splitter: Account<'info, Splitter>,
+
+    /// Splitter's event, cancelled through ticket-nft-core `cancel_event`
+    #[account(
+        constraint = event.key() == splitter.event @ SplitterError::TicketEventMismatch,
+        constraint = event.cancelled @ SplitterError::EventNotCancelled
+    )]
+    pub event: Box<Account<'info, Event>>,
+}
+
+/// Hold back every unclaimed payout once the event is called off, turning what is left into the
+/// refund reserve. Anyone can crank this.
+pub fn handler(ctx: Context<CancelSplitter>) -> Result<()> {
+    let splitter = &mut ctx.accounts.splitter;
+    require!(splitter.status != SplitterStatus::Cancelled, SplitterError::SplitterNotActive);
+
+    splitter.status = SplitterStatus::Cancelled;
+    let held_back = splitter.reserve()?;
+
+    emit!(SplitterCancelled {
+        splitter: splitter.key(),
+        event: splitter.event,
+        held_back,
+    });
+
+    msg!("Splitter for event {} cancelled, {} held back for refunds", splitter.event, held_back);
+
+    Ok(())
+}
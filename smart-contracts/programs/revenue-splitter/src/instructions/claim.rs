@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, TransferChecked};
+use ticket_nft_core::state::Event;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct Claim<'info> {
+    #[account(
+        mut,
+        seeds = [b"splitter", splitter.event.as_ref()],
+        bump = splitter.bump
+    )]
+    pub splitter: Account<'info, Splitter>,
+
+    /// Splitter's event; payouts are held back from the moment it's cancelled
+    #[account(
+        constraint = event.key() == splitter.event @ SplitterError::TicketEventMismatch,
+        constraint = !event.cancelled @ SplitterError::PayoutsHeldBack
+    )]
+    pub event: Box<Account<'info, Event>>,
+
+    #[account(mut)]
+    pub shareholder: Signer<'info>,
+
+    /// Splitter's payment mint, for token revenue
+    pub payment_mint: Option<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", splitter.key().as_ref()],
+        bump
+    )]
+    pub vault: Option<Account<'info, TokenAccount>>,
+
+    /// Shareholder's token account receiving token revenue
+    #[account(
+        mut,
+        constraint = shareholder_token_account.owner == shareholder.key() @ SplitterError::InvalidPaymentAccount
+    )]
+    pub shareholder_token_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Withdraw everything the signer has unlocked since their last claim
+pub fn handler(ctx: Context<Claim>) -> Result<()> {
+    let clock = Clock::get()?;
+    let splitter = &ctx.accounts.splitter;
+    require!(splitter.status != SplitterStatus::Cancelled, SplitterError::PayoutsHeldBack);
+
+    let index = splitter.shareholders
+        .iter()
+        .position(|holder| holder.wallet == ctx.accounts.shareholder.key())
+        .ok_or(SplitterError::NotAShareholder)?;
+
+    let holder = &splitter.shareholders[index];
+    let amount = splitter.claimable(holder, clock.unix_timestamp)?;
+    require!(amount > 0, SplitterError::NothingToClaim);
+
+    match splitter.check_payment_mint(&ctx.accounts.payment_mint)? {
+        None => {
+            splitter.sub_lamports(amount)?;
+            ctx.accounts.shareholder.add_lamports(amount)?;
+        }
+        Some(mint) => {
+            let vault = ctx.accounts.vault.as_ref().ok_or(SplitterError::InvalidPaymentAccount)?;
+            let to = ctx.accounts.shareholder_token_account
+                .as_ref()
+                .ok_or(SplitterError::InvalidPaymentAccount)?;
+
+            let seeds = &[
+                b"splitter",
+                splitter.event.as_ref(),
+                &[splitter.bump],
+            ];
+            let signer = &[&seeds[..]];
+
+            token::transfer_checked(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    TransferChecked {
+                        from: vault.to_account_info(),
+                        mint: mint.to_account_info(),
+                        to: to.to_account_info(),
+                        authority: splitter.to_account_info(),
+                    },
+                    signer,
+                ),
+                amount,
+                mint.decimals,
+            )?;
+        }
+    }
+
+    let splitter = &mut ctx.accounts.splitter;
+    let holder = &mut splitter.shareholders[index];
+    holder.claimed = holder.claimed
+        .checked_add(amount)
+        .ok_or(SplitterError::CalculationOverflow)?;
+    splitter.total_claimed = splitter.total_claimed
+        .checked_add(amount)
+        .ok_or(SplitterError::CalculationOverflow)?;
+
+    emit!(RevenueClaimed {
+        splitter: splitter.key(),
+        shareholder: ctx.accounts.shareholder.key(),
+        amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount, TransferChecked};
+use ticket_nft_core::state::TicketData;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct ClaimRefund<'info> {
+    #[account(
+        mut,
+        seeds = [b"splitter", splitter.event.as_ref()],
+        bump = splitter.bump,
+        constraint = splitter.status == SplitterStatus::Cancelled @ SplitterError::RefundsNotOpen
+    )]
+    pub splitter: Account<'info, Splitter>,
+
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    /// Ticket being refunded; burned so it can't be refunded twice
+    #[account(mut)]
+    pub ticket_mint: Account<'info, Mint>,
+
+    /// Ticket metadata from ticket-nft-core holding the face value
+    #[account(
+        seeds = [b"ticket", ticket_mint.key().as_ref()],
+        bump = ticket_data.bump,
+        seeds::program = ticket_nft_core::ID,
+        constraint = ticket_data.event == splitter.event @ SplitterError::TicketEventMismatch
+    )]
+    pub ticket_data: Account<'info, TicketData>,
+
+    #[account(
+        mut,
+        constraint = holder_ticket_account.mint == ticket_mint.key(),
+        constraint = holder_ticket_account.owner == holder.key(),
+        constraint = holder_ticket_account.amount == 1
+    )]
+    pub holder_ticket_account: Account<'info, TokenAccount>,
+
+    /// Splitter's payment mint, for token refunds
+    pub payment_mint: Option<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", splitter.key().as_ref()],
+        bump
+    )]
+    pub vault: Option<Account<'info, TokenAccount>>,
+
+    /// Holder's token account receiving a token refund
+    #[account(
+        mut,
+        constraint = holder_payment_account.owner == holder.key() @ SplitterError::InvalidPaymentAccount
+    )]
+    pub holder_payment_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Burn a ticket of a cancelled event and get its face value back from the refund reserve
+pub fn handler(ctx: Context<ClaimRefund>) -> Result<()> {
+    let amount = ctx.accounts.ticket_data.face_value;
+    ctx.accounts.splitter.record_refund(amount)?;
+    let splitter = &ctx.accounts.splitter;
+
+    token::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.ticket_mint.to_account_info(),
+                from: ctx.accounts.holder_ticket_account.to_account_info(),
+                authority: ctx.accounts.holder.to_account_info(),
+            },
+        ),
+        1,
+    )?;
+
+    match splitter.check_payment_mint(&ctx.accounts.payment_mint)? {
+        None => {
+            splitter.sub_lamports(amount)?;
+            ctx.accounts.holder.add_lamports(amount)?;
+        }
+        Some(mint) => {
+            let vault = ctx.accounts.vault.as_ref().ok_or(SplitterError::InvalidPaymentAccount)?;
+            let to = ctx.accounts.holder_payment_account
+                .as_ref()
+                .ok_or(SplitterError::InvalidPaymentAccount)?;
+
+            let seeds = &[
+                b"splitter",
+                splitter.event.as_ref(),
+                &[splitter.bump],
+            ];
+            let signer = &[&seeds[..]];
+
+            token::transfer_checked(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    TransferChecked {
+                        from: vault.to_account_info(),
+                        mint: mint.to_account_info(),
+                        to: to.to_account_info(),
+                        authority: splitter.to_account_info(),
+                    },
+                    signer,
+                ),
+                amount,
+                mint.decimals,
+            )?;
+        }
+    }
+
+    emit!(RefundClaimed {
+        splitter: splitter.key(),
+        holder: ctx.accounts.holder.key(),
+        ticket_mint: ctx.accounts.ticket_mint.key(),
+        amount,
+    });
+
+    Ok(())
+}
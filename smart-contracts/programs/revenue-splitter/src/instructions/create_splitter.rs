@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use ticket_nft_core::state::Event;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct CreateSplitter<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = Splitter::MAX_SIZE,
+        seeds = [b"splitter", event.key().as_ref()],
+        bump
+    )]
+    pub splitter: Account<'info, Splitter>,
+
+    /// Event whose primary sales feed this splitter; only its organizer can set one up
+    #[account(
+        seeds = [b"event", event.authority.as_ref(), &event.event_id.to_le_bytes()],
+        bump = event.bump,
+        seeds::program = ticket_nft_core::ID,
+        has_one = authority @ SplitterError::Unauthorized
+    )]
+    pub event: Account<'info, Event>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The event's payment mint, for events priced in an SPL token
+    pub payment_mint: Option<Account<'info, Mint>>,
+
+    /// Token vault holding undistributed revenue of a token-priced event
+    #[account(
+        init,
+        payer = authority,
+        token::mint = payment_mint,
+        token::authority = splitter,
+        seeds = [b"vault", splitter.key().as_ref()],
+        bump
+    )]
+    pub vault: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn handler(ctx: Context<CreateSplitter>, shares: Vec<ShareWeight>) -> Result<()> {
+    let clock = Clock::get()?;
+
+    // Validate shares: every deposit must be split completely, once per wallet
+    require!(
+        !shares.is_empty() && shares.len() <= Splitter::MAX_SHAREHOLDERS,
+        SplitterError::InvalidShares
+    );
+
+    let mut total_bps: u32 = 0;
+    for (i, share) in shares.iter().enumerate() {
+        require!(share.bps > 0, SplitterError::InvalidShares);
+        require!(
+            shares[..i].iter().all(|other| other.wallet != share.wallet),
+            SplitterError::InvalidShares
+        );
+        total_bps += share.bps as u32;
+
+        if !share.schedule.is_empty() {
+            require!(share.schedule.len() <= Shareholder::MAX_TRANCHES, SplitterError::InvalidSchedule);
+            let schedule_bps: u32 = share.schedule.iter().map(|tranche| tranche.bps as u32).sum();
+            require!(schedule_bps == Splitter::TOTAL_BPS as u32, SplitterError::InvalidSchedule);
+        }
+    }
+    require!(total_bps == Splitter::TOTAL_BPS as u32, SplitterError::InvalidShares);
+
+    // Revenue is held in the event's currency
+    let event = &ctx.accounts.event;
+    match (event.payment_mint, &ctx.accounts.payment_mint) {
+        (None, None) => {}
+        (Some(expected), Some(mint)) if mint.key() == expected => {
+            require!(ctx.accounts.vault.is_some(), SplitterError::InvalidPaymentAccount);
+        }
+        _ => return err!(SplitterError::PaymentMintMismatch),
+    }
+
+    let splitter = &mut ctx.accounts.splitter;
+    splitter.authority = ctx.accounts.authority.key();
+    splitter.event = event.key();
+    splitter.payment_mint = event.payment_mint;
+    splitter.shareholders = shares
+        .iter()
+        .map(|share| Shareholder {
+            wallet: share.wallet,
+            bps: share.bps,
+            schedule: share.schedule.clone(),
+            claimed: 0,
+        })
+        .collect();
+    splitter.revenue_per_bps = 0;
+    splitter.total_deposited = 0;
+    splitter.total_claimed = 0;
+    splitter.total_refunded = 0;
+    splitter.status = SplitterStatus::Active;
+    splitter.created_at = clock.unix_timestamp;
+    splitter.bump = ctx.bumps.splitter;
+
+    emit!(SplitterCreated {
+        splitter: splitter.key(),
+        event: splitter.event,
+        authority: splitter.authority,
+        shares,
+    });
+
+    msg!("Splitter created for event {} with {} shareholders", splitter.event, splitter.shareholders.len());
+
+    Ok(())
+}
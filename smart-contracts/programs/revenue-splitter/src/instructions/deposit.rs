@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, TransferChecked};
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(
+        mut,
+        seeds = [b"splitter", splitter.event.as_ref()],
+        bump = splitter.bump
+    )]
+    pub splitter: Account<'info, Splitter>,
+
+    /// Anyone can pay revenue in, typically the primary-sale flow
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+
+    /// Splitter's payment mint, for token revenue
+    pub payment_mint: Option<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", splitter.key().as_ref()],
+        bump
+    )]
+    pub vault: Option<Account<'info, TokenAccount>>,
+
+    /// Depositor's token account paying token revenue
+    #[account(
+        mut,
+        constraint = depositor_token_account.owner == depositor.key() @ SplitterError::InvalidPaymentAccount
+    )]
+    pub depositor_token_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+    require!(amount > 0, SplitterError::InvalidAmount);
+
+    match ctx.accounts.splitter.check_payment_mint(&ctx.accounts.payment_mint)? {
+        None => {
+            // SOL revenue sits on the splitter account itself
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.depositor.to_account_info(),
+                        to: ctx.accounts.splitter.to_account_info(),
+                    },
+                ),
+                amount,
+            )?;
+        }
+        Some(mint) => {
+            let vault = ctx.accounts.vault.as_ref().ok_or(SplitterError::InvalidPaymentAccount)?;
+            let from = ctx.accounts.depositor_token_account
+                .as_ref()
+                .ok_or(SplitterError::InvalidPaymentAccount)?;
+
+            token::transfer_checked(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    TransferChecked {
+                        from: from.to_account_info(),
+                        mint: mint.to_account_info(),
+                        to: vault.to_account_info(),
+                        authority: ctx.accounts.depositor.to_account_info(),
+                    },
+                ),
+                amount,
+                mint.decimals,
+            )?;
+        }
+    }
+
+    let splitter = &mut ctx.accounts.splitter;
+    splitter.record_deposit(amount)?;
+
+    emit!(RevenueDeposited {
+        splitter: splitter.key(),
+        depositor: ctx.accounts.depositor.key(),
+        amount,
+        total_deposited: splitter.total_deposited,
+    });
+
+    Ok(())
+}
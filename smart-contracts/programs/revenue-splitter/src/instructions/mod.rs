@@ -0,0 +1,16 @@
+// Every module exposes its own `handler`; lib.rs always calls them by full path.
+#![allow(ambiguous_glob_reexports)]
+
+pub mod create_splitter;
+pub mod deposit;
+pub mod claim;
+pub mod settle_splitter;
+pub mod cancel_splitter;
+pub mod claim_refund;
+
+pub use create_splitter::*;
+pub use deposit::*;
+pub use claim::*;
+pub use settle_splitter::*;
+pub use cancel_splitter::*;
+pub use claim_refund::*;
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use ticket_nft_core::state::Event;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SettleSplitter<'info> {
+    #[account(
+        mut,
+        seeds = [b"splitter", splitter.event.as_ref()],
+        bump = splitter.bump,
+        has_one = authority @ SplitterError::Unauthorized
+    )]
+    pub splitter: Account<'info, Splitter>,
+
+    /// Splitter's event, which must have ended without being cancelled
+    #[account(
+        constraint = event.key() == splitter.event @ SplitterError::TicketEventMismatch,
+        constraint = !event.cancelled @ SplitterError::PayoutsHeldBack
+    )]
+    pub event: Box<Account<'info, Event>>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Event authority confirms the event went ahead, releasing every `OnSettle` tranche
+pub fn handler(ctx: Context<SettleSplitter>) -> Result<()> {
+    let clock = Clock::get()?;
+    require!(clock.unix_timestamp >= ctx.accounts.event.end_time, SplitterError::EventNotOver);
+
+    let splitter = &mut ctx.accounts.splitter;
+    require!(splitter.status == SplitterStatus::Active, SplitterError::SplitterNotActive);
+
+    splitter.status = SplitterStatus::Settled;
+
+    emit!(SplitterSettled {
+        splitter: splitter.key(),
+        event: splitter.event,
+    });
+
+    msg!("Splitter for event {} settled", splitter.event);
+
+    Ok(())
+}
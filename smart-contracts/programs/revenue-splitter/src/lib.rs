@@ -1,16 +1,45 @@
 use anchor_lang::prelude::*;
 
+pub mod instructions;
+pub mod state;
+pub mod errors;
+
+use instructions::*;
+use state::*;
+
+// Anchor's template program ID, shared with ticket-nft-core until keys are generated; replace
+// it with this program's key from `anchor keys list` before deploying
 declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
 
 #[program]
 pub mod revenue_splitter {
     use super::*;
 
-    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
-        msg!("revenue-splitter initialized!");
-        Ok(())
+    // Splitter setup
+    pub fn create_splitter(ctx: Context<CreateSplitter>, shares: Vec<ShareWeight>) -> Result<()> {
+        instructions::create_splitter::handler(ctx, shares)
+    }
+
+    // Revenue
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        instructions::deposit::handler(ctx, amount)
+    }
+
+    pub fn claim(ctx: Context<Claim>) -> Result<()> {
+        instructions::claim::handler(ctx)
     }
-}
 
-#[derive(Accounts)]
-pub struct Initialize {}
+    // Payout milestones
+    pub fn settle_splitter(ctx: Context<SettleSplitter>) -> Result<()> {
+        instructions::settle_splitter::handler(ctx)
+    }
+
+    pub fn cancel_splitter(ctx: Context<CancelSplitter>) -> Result<()> {
+        instructions::cancel_splitter::handler(ctx)
+    }
+
+    // Refunds
+    pub fn claim_refund(ctx: Context<ClaimRefund>) -> Result<()> {
+        instructions::claim_refund::handler(ctx)
+    }
+}
@@ -0,0 +1,227 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+use crate::errors::*;
+
+/// Pull-based splitter for an event's primary-sale revenue, seeded by `[b"splitter", event]`.
+///
+/// Deposits bump `revenue_per_bps`, the cumulative revenue owed to each basis point of shares;
+/// a shareholder's accrued balance is `bps * revenue_per_bps` minus what they already claimed,
+/// so deposits cost the same no matter how many shareholders there are.
+///
+/// Each shareholder's accrual vests through its `schedule`; only the unlocked fraction can be
+/// claimed, and nothing can while the splitter is cancelled: once the event is called off, what
+/// is left becomes a refund reserve ticket holders draw their face value from.
+#[account]
+pub struct Splitter {
+    pub authority: Pubkey,        // Event organizer who set up the splitter
+    pub event: Pubkey,            // ticket-nft-core Event whose revenue is split
+    pub payment_mint: Option<Pubkey>, // SPL mint revenue is held in (None = lamports), from the event
+    pub shareholders: Vec<Shareholder>,
+    pub revenue_per_bps: u128,    // Cumulative revenue per bps, scaled by PRECISION
+    pub total_deposited: u64,
+    pub total_claimed: u64,
+    pub total_refunded: u64,      // Face value paid back to holders of a cancelled event
+    pub status: SplitterStatus,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl Splitter {
+    pub const MAX_SHAREHOLDERS: usize = 10;
+    pub const TOTAL_BPS: u16 = 10000;
+    pub const PRECISION: u128 = 1_000_000_000_000;
+
+    pub const MAX_SIZE: usize = 8 + // discriminator
+        32 + // authority
+        32 + // event
+        1 + 32 + // payment_mint
+        4 + (Shareholder::SIZE * Self::MAX_SHAREHOLDERS) + // shareholders
+        16 + // revenue_per_bps
+        8 + // total_deposited
+        8 + // total_claimed
+        8 + // total_refunded
+        1 + // status
+        8 + // created_at
+        1; // bump
+
+    /// Credit a deposit to every shareholder at once
+    pub fn record_deposit(&mut self, amount: u64) -> Result<()> {
+        let per_bps = (amount as u128)
+            .checked_mul(Self::PRECISION)
+            .ok_or(SplitterError::CalculationOverflow)?
+            / Self::TOTAL_BPS as u128;
+
+        self.revenue_per_bps = self.revenue_per_bps
+            .checked_add(per_bps)
+            .ok_or(SplitterError::CalculationOverflow)?;
+        self.total_deposited = self.total_deposited
+            .checked_add(amount)
+            .ok_or(SplitterError::CalculationOverflow)?;
+        Ok(())
+    }
+
+    /// Check the mint passed to an instruction against the splitter's currency.
+    /// Returns None for SOL splitters.
+    pub fn check_payment_mint<'a, 'info>(
+        &self,
+        provided: &'a Option<Account<'info, Mint>>,
+    ) -> Result<Option<&'a Account<'info, Mint>>> {
+        match (self.payment_mint, provided) {
+            (None, None) => Ok(None),
+            (Some(expected), Some(mint)) if mint.key() == expected => Ok(Some(mint)),
+            _ => err!(SplitterError::PaymentMintMismatch),
+        }
+    }
+
+    /// Revenue still held by the splitter; after a cancellation this is the refund reserve
+    pub fn reserve(&self) -> Result<u64> {
+        self.total_deposited
+            .checked_sub(self.total_claimed)
+            .and_then(|left| left.checked_sub(self.total_refunded))
+            .ok_or(error!(SplitterError::CalculationOverflow))
+    }
+
+    /// Draw a ticket's face value from the refund reserve
+    pub fn record_refund(&mut self, amount: u64) -> Result<()> {
+        require!(amount <= self.reserve()?, SplitterError::InsufficientRefundReserve);
+        self.total_refunded = self.total_refunded
+            .checked_add(amount)
+            .ok_or(SplitterError::CalculationOverflow)?;
+        Ok(())
+    }
+
+    /// Basis points of `shareholder`'s accrual released at `now`
+    pub fn unlocked_bps(&self, shareholder: &Shareholder, now: i64) -> u16 {
+        if shareholder.schedule.is_empty() {
+            return Self::TOTAL_BPS;
+        }
+
+        shareholder.schedule
+            .iter()
+            .filter(|tranche| match tranche.unlock {
+                Unlock::At(timestamp) => now >= timestamp,
+                Unlock::OnSettle => self.status == SplitterStatus::Settled,
+            })
+            .map(|tranche| tranche.bps)
+            .sum()
+    }
+
+    /// What `shareholder` can withdraw right now
+    pub fn claimable(&self, shareholder: &Shareholder, now: i64) -> Result<u64> {
+        if self.status == SplitterStatus::Cancelled {
+            return Ok(0);
+        }
+
+        let unlocked = (self.accrued(shareholder)? as u128)
+            .checked_mul(self.unlocked_bps(shareholder, now) as u128)
+            .ok_or(SplitterError::CalculationOverflow)?
+            / Self::TOTAL_BPS as u128;
+
+        // A tranche can't re-lock, so unlocked never drops below what was already claimed
+        Ok((unlocked as u64).saturating_sub(shareholder.claimed))
+    }
+
+    /// Everything `shareholder` has earned so far, claimed or not
+    pub fn accrued(&self, shareholder: &Shareholder) -> Result<u64> {
+        let accrued = (shareholder.bps as u128)
+            .checked_mul(self.revenue_per_bps)
+            .ok_or(SplitterError::CalculationOverflow)?
+            / Self::PRECISION;
+        u64::try_from(accrued).map_err(|_| error!(SplitterError::CalculationOverflow))
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct Shareholder {
+    pub wallet: Pubkey,
+    pub bps: u16,                 // Share of every deposit, out of 10000
+    pub schedule: Vec<Tranche>,   // Vesting tranches; empty = everything unlocked immediately
+    pub claimed: u64,             // Total withdrawn so far
+}
+
+impl Shareholder {
+    pub const MAX_TRANCHES: usize = 4;
+
+    pub const SIZE: usize = 32 + // wallet
+        2 + // bps
+        4 + (Tranche::SIZE * Self::MAX_TRANCHES) + // schedule
+        8; // claimed
+}
+
+/// Shareholder entry passed to `create_splitter`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct ShareWeight {
+    pub wallet: Pubkey,
+    pub bps: u16,
+    pub schedule: Vec<Tranche>,
+}
+
+/// Part of a shareholder's accrual released once `unlock` is reached
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct Tranche {
+    pub bps: u16,                 // Fraction of the shareholder's accrual, out of 10000
+    pub unlock: Unlock,
+}
+
+impl Tranche {
+    pub const SIZE: usize = 2 + // bps
+        1 + 8; // unlock
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum Unlock {
+    At(i64),                      // Unix timestamp, e.g. on-sale or the event's end time
+    OnSettle,                     // When the event authority settles the splitter
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum SplitterStatus {
+    Active,
+    Settled,                      // Event went ahead; OnSettle tranches are released
+    Cancelled,                    // Event called off; payouts held back, refunds open
+}
+
+#[event]
+pub struct SplitterCreated {
+    pub splitter: Pubkey,
+    pub event: Pubkey,
+    pub authority: Pubkey,
+    pub shares: Vec<ShareWeight>,
+}
+
+#[event]
+pub struct RevenueDeposited {
+    pub splitter: Pubkey,
+    pub depositor: Pubkey,
+    pub amount: u64,
+    pub total_deposited: u64,
+}
+
+#[event]
+pub struct SplitterSettled {
+    pub splitter: Pubkey,
+    pub event: Pubkey,
+}
+
+#[event]
+pub struct SplitterCancelled {
+    pub splitter: Pubkey,
+    pub event: Pubkey,
+    pub held_back: u64,
+}
+
+#[event]
+pub struct RefundClaimed {
+    pub splitter: Pubkey,
+    pub holder: Pubkey,
+    pub ticket_mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct RevenueClaimed {
+    pub splitter: Pubkey,
+    pub shareholder: Pubkey,
+    pub amount: u64,
+}
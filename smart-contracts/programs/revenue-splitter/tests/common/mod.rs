@@ -0,0 +1,199 @@
+//! Fixtures shared by the splitter tests, and a stand-in runtime that runs instructions
+//! through their `try_accounts` validation and handler the way the program entrypoint does.
+
+#![allow(dead_code)]
+
+use std::collections::BTreeSet;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::SUCCESS;
+use anchor_lang::solana_program::program_stubs;
+use anchor_lang::Bumps;
+use anchor_spl::token::spl_token;
+
+use revenue_splitter::instructions::{claim, settle_splitter, Claim, SettleSplitter};
+use revenue_splitter::state::*;
+use ticket_nft_core::state::Event;
+
+/// Lamports every test account starts with unless set otherwise
+pub const LAMPORTS: u64 = 10_000_000_000;
+
+pub fn shareholder(bps: u16, schedule: Vec<Tranche>) -> Shareholder {
+    Shareholder {
+        wallet: Pubkey::new_unique(),
+        bps,
+        schedule,
+        claimed: 0,
+    }
+}
+
+pub fn splitter(shareholders: Vec<Shareholder>) -> Splitter {
+    Splitter {
+        authority: Pubkey::new_unique(),
+        event: Pubkey::new_unique(),
+        payment_mint: None,
+        shareholders,
+        revenue_per_bps: 0,
+        total_deposited: 0,
+        total_claimed: 0,
+        total_refunded: 0,
+        status: SplitterStatus::Active,
+        created_at: 0,
+        bump: 255,
+    }
+}
+
+pub fn event(authority: Pubkey, end_time: i64, cancelled: bool) -> Event {
+    Event {
+        authority,
+        event_id: 0,
+        name: String::new(),
+        venue: String::new(),
+        start_time: end_time - 3600,
+        end_time,
+        tiers: vec![],
+        payment_mint: None,
+        total_supply: 0,
+        tickets_minted: 0,
+        cancelled,
+        created_at: 0,
+        bump: 0,
+    }
+}
+
+pub struct TestAccount {
+    pub key: Pubkey,
+    pub owner: Pubkey,
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub signer: bool,
+    pub executable: bool,
+}
+
+impl TestAccount {
+    pub fn new(key: Pubkey, owner: Pubkey, data: Vec<u8>) -> Self {
+        Self { key, owner, lamports: LAMPORTS, data, signer: false, executable: false }
+    }
+
+    pub fn signer(key: Pubkey) -> Self {
+        Self { signer: true, ..Self::new(key, anchor_lang::system_program::ID, vec![]) }
+    }
+
+    pub fn program(key: Pubkey) -> Self {
+        Self { executable: true, ..Self::new(key, Pubkey::new_unique(), vec![]) }
+    }
+
+    /// Stand-in for an optional account left out of the instruction
+    pub fn absent() -> Self {
+        Self::new(revenue_splitter::ID, Pubkey::default(), vec![])
+    }
+
+    pub fn owned_by<T: AccountSerialize>(owner: Pubkey, key: Pubkey, account: &T) -> Self {
+        let mut data = vec![];
+        account.try_serialize(&mut data).unwrap();
+        Self::new(key, owner, data)
+    }
+
+    pub fn token_program() -> Self {
+        Self::program(spl_token::ID)
+    }
+
+    pub fn with_lamports(self, lamports: u64) -> Self {
+        Self { lamports, ..self }
+    }
+}
+
+/// Account infos over `accounts`, leaked so instructions can borrow them for `'static`
+pub fn infos(accounts: impl IntoIterator<Item = TestAccount>) -> &'static [AccountInfo<'static>] {
+    let accounts: &'static mut [TestAccount] = Vec::leak(accounts.into_iter().collect());
+    let infos = accounts
+        .iter_mut()
+        .map(|a| AccountInfo {
+            key: &a.key,
+            is_signer: a.signer,
+            is_writable: true,
+            lamports: std::rc::Rc::new(std::cell::RefCell::new(&mut a.lamports)),
+            data: std::rc::Rc::new(std::cell::RefCell::new(&mut a.data[..])),
+            owner: &a.owner,
+            executable: a.executable,
+            rent_epoch: 0,
+        })
+        .collect::<Vec<_>>();
+    Vec::leak(infos)
+}
+
+/// Clock and Rent for `Sysvar::get`, with the clock at 0
+struct Runtime;
+
+impl program_stubs::SyscallStubs for Runtime {
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { *(var_addr as *mut Clock) = Clock::default() };
+        SUCCESS
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { *(var_addr as *mut Rent) = Rent::default() };
+        SUCCESS
+    }
+}
+
+/// Run an instruction end to end: validate `accounts`, call `handler`, then write the
+/// accounts back. Returns the accounts to inspect.
+pub fn run<T, const N: usize>(
+    accounts: [TestAccount; N],
+    handler: impl FnOnce(Context<'_, '_, 'static, 'static, T>) -> Result<()>,
+) -> Result<T>
+where
+    T: Bumps + Accounts<'static, T::Bumps> + AccountsExit<'static>,
+    T::Bumps: Default,
+{
+    static RUNTIME: std::sync::Once = std::sync::Once::new();
+    RUNTIME.call_once(|| {
+        program_stubs::set_syscall_stubs(Box::new(Runtime));
+    });
+
+    let mut bumps = Default::default();
+    let mut accounts = T::try_accounts(
+        &revenue_splitter::ID,
+        &mut infos(accounts),
+        &[],
+        &mut bumps,
+        &mut BTreeSet::new(),
+    )?;
+    handler(Context::new(&revenue_splitter::ID, &mut accounts, &[], bumps))?;
+    accounts.exit(&revenue_splitter::ID)?;
+    Ok(accounts)
+}
+
+/// A splitter account at its PDA for `splitter.event`, holding `deposited` lamports of revenue
+pub fn splitter_account(splitter: &Splitter, deposited: u64) -> TestAccount {
+    let (key, bump) = Pubkey::find_program_address(&[b"splitter", splitter.event.as_ref()], &revenue_splitter::ID);
+    TestAccount::owned_by(revenue_splitter::ID, key, &Splitter { bump, ..splitter.clone() })
+        .with_lamports(Rent::default().minimum_balance(Splitter::MAX_SIZE) + deposited)
+}
+
+pub fn event_account(key: Pubkey, event: &Event) -> TestAccount {
+    TestAccount::owned_by(ticket_nft_core::ID, key, event)
+}
+
+/// Run `claim` for `wallet` on a SOL splitter holding `deposited`, passing `event` at `event_key`
+pub fn run_claim(splitter: &Splitter, deposited: u64, event_key: Pubkey, event: &Event, wallet: Pubkey) -> Result<Claim<'static>> {
+    run::<Claim, 7>([
+        splitter_account(splitter, deposited),
+        event_account(event_key, event),
+        TestAccount::signer(wallet),
+        TestAccount::absent(),
+        TestAccount::absent(),
+        TestAccount::absent(),
+        TestAccount::token_program(),
+    ], claim::handler)
+}
+
+/// Run `settle_splitter` as the splitter's authority, passing `event` at `event_key`
+pub fn run_settle_splitter(splitter: &Splitter, event_key: Pubkey, event: &Event) -> Result<SettleSplitter<'static>> {
+    run::<SettleSplitter, 3>([
+        splitter_account(splitter, 0),
+        event_account(event_key, event),
+        TestAccount::signer(splitter.authority),
+    ], settle_splitter::handler)
+}
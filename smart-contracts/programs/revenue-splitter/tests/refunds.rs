@@ -0,0 +1,58 @@
+mod common;
+
+use revenue_splitter::errors::SplitterError;
+use revenue_splitter::state::*;
+
+use common::*;
+
+fn cancelled_splitter(deposited: u64, claimed: u64) -> Splitter {
+    let mut splitter = splitter(vec![Shareholder { claimed, ..shareholder(10_000, vec![]) }]);
+    splitter.total_claimed = claimed;
+    splitter.record_deposit(deposited).unwrap();
+    splitter.status = SplitterStatus::Cancelled;
+    splitter
+}
+
+#[test]
+fn refunds_draw_the_reserve_down_to_zero() {
+    let mut splitter = cancelled_splitter(1_000, 400);
+    assert_eq!(splitter.reserve().unwrap(), 600);
+
+    splitter.record_refund(250).unwrap();
+    splitter.record_refund(350).unwrap();
+
+    assert_eq!(splitter.total_refunded, 600);
+    assert_eq!(splitter.reserve().unwrap(), 0);
+}
+
+#[test]
+fn a_refund_the_reserve_cannot_cover_is_refused() {
+    let mut splitter = cancelled_splitter(1_000, 400);
+    splitter.record_refund(500).unwrap();
+
+    assert_eq!(
+        splitter.record_refund(101),
+        Err(SplitterError::InsufficientRefundReserve.into())
+    );
+    assert_eq!(splitter.total_refunded, 500);
+    assert_eq!(splitter.reserve().unwrap(), 100);
+}
+
+#[test]
+fn nothing_can_be_claimed_between_cancel_event_and_cancel_splitter() {
+    let mut splitter = splitter(vec![shareholder(10_000, vec![])]);
+    splitter.record_deposit(1_000).unwrap();
+    let wallet = splitter.shareholders[0].wallet;
+
+    // The event has been cancelled but nobody has cranked `cancel_splitter` yet
+    let cancelled = event(splitter.authority, 3_600, true);
+    assert!(splitter.status == SplitterStatus::Active);
+    assert_eq!(
+        run_claim(&splitter, 1_000, splitter.event, &cancelled, wallet).err(),
+        Some(SplitterError::PayoutsHeldBack.into())
+    );
+
+    let running = event(splitter.authority, 3_600, false);
+    let claimed = run_claim(&splitter, 1_000, splitter.event, &running, wallet).unwrap();
+    assert_eq!(claimed.shareholder.lamports(), LAMPORTS + 1_000);
+}
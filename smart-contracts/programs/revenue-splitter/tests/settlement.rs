@@ -0,0 +1,63 @@
+mod common;
+
+use anchor_lang::prelude::*;
+
+use revenue_splitter::errors::SplitterError;
+use revenue_splitter::state::*;
+
+use common::*;
+
+fn on_settle_splitter() -> Splitter {
+    splitter(vec![shareholder(10_000, vec![Tranche { bps: 10_000, unlock: Unlock::OnSettle }])])
+}
+
+#[test]
+fn a_splitter_settles_once_its_event_has_ended() {
+    let splitter = on_settle_splitter();
+
+    let running = event(splitter.authority, 1, false);
+    assert_eq!(
+        run_settle_splitter(&splitter, splitter.event, &running).err(),
+        Some(SplitterError::EventNotOver.into())
+    );
+
+    let ended = event(splitter.authority, 0, false);
+    let settled = run_settle_splitter(&splitter, splitter.event, &ended).unwrap();
+    assert!(settled.splitter.status == SplitterStatus::Settled);
+}
+
+#[test]
+fn a_cancelled_event_cannot_be_settled() {
+    let splitter = on_settle_splitter();
+    let cancelled = event(splitter.authority, -60, true);
+
+    assert_eq!(
+        run_settle_splitter(&splitter, splitter.event, &cancelled).err(),
+        Some(SplitterError::PayoutsHeldBack.into())
+    );
+}
+
+#[test]
+fn settle_and_claim_only_take_the_splitters_own_event() {
+    let splitter = on_settle_splitter();
+    let other_event = event(splitter.authority, -60, false);
+    let mismatch = Some(SplitterError::TicketEventMismatch.into());
+
+    assert_eq!(run_settle_splitter(&splitter, Pubkey::new_unique(), &other_event).err(), mismatch);
+    assert_eq!(
+        run_claim(&splitter, 0, Pubkey::new_unique(), &other_event, splitter.shareholders[0].wallet).err(),
+        mismatch
+    );
+}
+
+#[test]
+fn a_settled_splitter_pays_out_its_on_settle_tranches() {
+    let mut splitter = on_settle_splitter();
+    splitter.record_deposit(1_000).unwrap();
+    splitter.status = SplitterStatus::Settled;
+    let wallet = splitter.shareholders[0].wallet;
+
+    let claimed = run_claim(&splitter, 1_000, splitter.event, &event(splitter.authority, -60, false), wallet).unwrap();
+    assert_eq!(claimed.shareholder.lamports(), LAMPORTS + 1_000);
+    assert_eq!(claimed.splitter.total_claimed, 1_000);
+}
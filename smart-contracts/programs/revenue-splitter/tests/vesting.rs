@@ -0,0 +1,67 @@
+mod common;
+
+use revenue_splitter::state::*;
+
+use common::*;
+
+/// What the claim instruction records after paying out
+fn claim(splitter: &mut Splitter, index: usize, now: i64) -> u64 {
+    let amount = splitter.claimable(&splitter.shareholders[index], now).unwrap();
+    splitter.shareholders[index].claimed += amount;
+    splitter.total_claimed += amount;
+    amount
+}
+
+#[test]
+fn deposits_accrue_to_shareholders_by_bps_across_claims() {
+    let mut splitter = splitter(vec![
+        shareholder(7_000, vec![]),
+        shareholder(3_000, vec![]),
+    ]);
+
+    splitter.record_deposit(1_000).unwrap();
+    assert_eq!(claim(&mut splitter, 0, 0), 700);
+
+    // A later deposit only pays out what accrued since the last claim
+    splitter.record_deposit(500).unwrap();
+    assert_eq!(claim(&mut splitter, 0, 0), 350);
+    assert_eq!(claim(&mut splitter, 1, 0), 450);
+    assert_eq!(claim(&mut splitter, 1, 0), 0);
+
+    assert_eq!(splitter.total_claimed, splitter.total_deposited);
+    assert_eq!(splitter.reserve().unwrap(), 0);
+}
+
+#[test]
+fn tranches_release_the_accrual_by_time_and_on_settle() {
+    let mut splitter = splitter(vec![shareholder(10_000, vec![
+        Tranche { bps: 2_500, unlock: Unlock::At(100) },
+        Tranche { bps: 2_500, unlock: Unlock::At(200) },
+        Tranche { bps: 5_000, unlock: Unlock::OnSettle },
+    ])]);
+    splitter.record_deposit(10_000).unwrap();
+
+    assert_eq!(claim(&mut splitter, 0, 99), 0);
+    assert_eq!(claim(&mut splitter, 0, 100), 2_500);
+    assert_eq!(claim(&mut splitter, 0, 250), 2_500);
+    assert_eq!(claim(&mut splitter, 0, 1_000), 0);
+
+    // Revenue deposited after a tranche unlocked vests at the same fraction
+    splitter.record_deposit(2_000).unwrap();
+    assert_eq!(claim(&mut splitter, 0, 1_000), 1_000);
+
+    splitter.status = SplitterStatus::Settled;
+    assert_eq!(splitter.unlocked_bps(&splitter.shareholders[0], 1_000), 10_000);
+    assert_eq!(claim(&mut splitter, 0, 1_000), 6_000);
+    assert_eq!(splitter.shareholders[0].claimed, 12_000);
+}
+
+#[test]
+fn nothing_is_claimable_once_the_splitter_is_cancelled() {
+    let mut splitter = splitter(vec![shareholder(10_000, vec![])]);
+    splitter.record_deposit(5_000).unwrap();
+    splitter.status = SplitterStatus::Cancelled;
+
+    assert_eq!(splitter.claimable(&splitter.shareholders[0], i64::MAX).unwrap(), 0);
+    assert_eq!(splitter.reserve().unwrap(), 5_000);
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum TicketError {
+    #[msg("Unauthorized operation")]
+    Unauthorized,
+
+    #[msg("Invalid event time window")]
+    InvalidEventTime,
+
+    #[msg("Event has already ended")]
+    EventEnded,
+
+    #[msg("Event has been cancelled")]
+    EventCancelled,
+
+    #[msg("Invalid ticket tier configuration")]
+    InvalidTiers,
+
+    #[msg("Ticket tier does not exist")]
+    InvalidTier,
+
+    #[msg("Ticket tier is sold out")]
+    TierSoldOut,
+
+    #[msg("Invalid seat assignment")]
+    InvalidSeat,
+
+    #[msg("String too long")]
+    StringTooLong,
+
+    #[msg("Calculation overflow")]
+    CalculationOverflow,
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct CancelEvent<'info> {
+    #[account(
+        mut,
+        seeds = [b"event", event.authority.as_ref(), &event.event_id.to_le_bytes()],
+        bump = event.bump,
+        has_one = authority @ TicketError::Unauthorized
+    )]
+    pub event: Account<'info, Event>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Call the event off. Irreversible: other programs key refunds and trading halts off this flag.
+pub fn handler(ctx: Context<CancelEvent>) -> Result<()> {
+    let event = &mut ctx.accounts.event;
+    require!(!event.cancelled, TicketError::EventCancelled);
+
+    event.cancelled = true;
+
+    emit!(EventCancelled {
+        event: event.key(),
+        authority: event.authority,
+        tickets_minted: event.tickets_minted,
+    });
+
+    msg!("Event {} cancelled", event.name);
+
+    Ok(())
+}
@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(event_id: u64)]
+pub struct CreateEvent<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = Event::MAX_SIZE,
+        seeds = [b"event", authority.key().as_ref(), &event_id.to_le_bytes()],
+        bump
+    )]
+    pub event: Account<'info, Event>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// SPL mint tickets are priced in (e.g. USDC); omit to price in SOL
+    pub payment_mint: Option<Account<'info, Mint>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<CreateEvent>,
+    event_id: u64,
+    name: String,
+    venue: String,
+    start_time: i64,
+    end_time: i64,
+    mut tiers: Vec<TicketTier>,
+) -> Result<()> {
+    let event = &mut ctx.accounts.event;
+    let clock = Clock::get()?;
+
+    // Validate inputs
+    require!(name.len() <= Event::MAX_NAME_LEN, TicketError::StringTooLong);
+    require!(venue.len() <= Event::MAX_VENUE_LEN, TicketError::StringTooLong);
+    require!(start_time < end_time, TicketError::InvalidEventTime);
+    require!(end_time > clock.unix_timestamp, TicketError::InvalidEventTime);
+    require!(
+        !tiers.is_empty() && tiers.len() <= Event::MAX_TIERS,
+        TicketError::InvalidTiers
+    );
+
+    let mut total_supply: u32 = 0;
+    for tier in tiers.iter_mut() {
+        require!(tier.name.len() <= TicketTier::MAX_NAME_LEN, TicketError::StringTooLong);
+        require!(tier.supply > 0, TicketError::InvalidTiers);
+        require!(tier.face_value > 0, TicketError::InvalidTiers);
+
+        total_supply = total_supply
+            .checked_add(tier.supply)
+            .ok_or(TicketError::CalculationOverflow)?;
+        tier.minted = 0;
+    }
+
+    event.authority = ctx.accounts.authority.key();
+    event.event_id = event_id;
+    event.name = name;
+    event.venue = venue;
+    event.start_time = start_time;
+    event.end_time = end_time;
+    event.tiers = tiers;
+    event.payment_mint = ctx.accounts.payment_mint.as_ref().map(|mint| mint.key());
+    event.total_supply = total_supply;
+    event.tickets_minted = 0;
+    event.cancelled = false;
+    event.created_at = clock.unix_timestamp;
+    event.bump = ctx.bumps.event;
+
+    emit!(EventCreated {
+        event: event.key(),
+        authority: event.authority,
+        event_id,
+        start_time,
+        total_supply,
+    });
+
+    msg!("Event {} created with {} tickets", event.name, total_supply);
+
+    Ok(())
+}
@@ -0,0 +1,167 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Mint, MintTo, SetAuthority, Token, TokenAccount};
+use anchor_spl::token::spl_token::instruction::AuthorityType;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(tier_index: u8, section: String, row: String, seat: String)]
+pub struct MintTicket<'info> {
+    #[account(
+        mut,
+        seeds = [b"event", event.authority.as_ref(), &event.event_id.to_le_bytes()],
+        bump = event.bump,
+        has_one = authority @ TicketError::Unauthorized
+    )]
+    pub event: Box<Account<'info, Event>>,
+
+    /// One mint per seat; the seeds make double-minting a seat impossible
+    #[account(
+        init,
+        payer = authority,
+        mint::decimals = 0,
+        mint::authority = event,
+        seeds = [
+            b"ticket_mint",
+            event.key().as_ref(),
+            &TicketData::seat_seed(&section, &row, &seat),
+        ],
+        bump
+    )]
+    pub ticket_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = TicketData::MAX_SIZE,
+        seeds = [b"ticket", ticket_mint.key().as_ref()],
+        bump
+    )]
+    pub ticket_data: Box<Account<'info, TicketData>>,
+
+    /// CHECK: Wallet receiving the ticket
+    pub recipient: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = ticket_mint,
+        associated_token::authority = recipient
+    )]
+    pub recipient_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn handler(
+    ctx: Context<MintTicket>,
+    tier_index: u8,
+    section: String,
+    row: String,
+    seat: String,
+) -> Result<()> {
+    let clock = Clock::get()?;
+
+    // Validate seat assignment
+    require!(
+        !section.is_empty() && !row.is_empty() && !seat.is_empty(),
+        TicketError::InvalidSeat
+    );
+    require!(section.len() <= TicketData::MAX_SEAT_FIELD_LEN, TicketError::StringTooLong);
+    require!(row.len() <= TicketData::MAX_SEAT_FIELD_LEN, TicketError::StringTooLong);
+    require!(seat.len() <= TicketData::MAX_SEAT_FIELD_LEN, TicketError::StringTooLong);
+
+    let event = &mut ctx.accounts.event;
+    require!(clock.unix_timestamp < event.end_time, TicketError::EventEnded);
+    require!(!event.cancelled, TicketError::EventCancelled);
+
+    let tier = event
+        .tiers
+        .get_mut(tier_index as usize)
+        .ok_or(TicketError::InvalidTier)?;
+    require!(tier.minted < tier.supply, TicketError::TierSoldOut);
+
+    tier.minted = tier.minted
+        .checked_add(1)
+        .ok_or(TicketError::CalculationOverflow)?;
+    let face_value = tier.face_value;
+
+    event.tickets_minted = event.tickets_minted
+        .checked_add(1)
+        .ok_or(TicketError::CalculationOverflow)?;
+
+    // Mint the single ticket token, then drop the mint authority so supply stays at 1
+    let authority_key = event.authority;
+    let event_id_bytes = event.event_id.to_le_bytes();
+    let seeds = &[
+        b"event",
+        authority_key.as_ref(),
+        event_id_bytes.as_ref(),
+        &[event.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    token::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.ticket_mint.to_account_info(),
+                to: ctx.accounts.recipient_token_account.to_account_info(),
+                authority: event.to_account_info(),
+            },
+            signer,
+        ),
+        1,
+    )?;
+
+    token::set_authority(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            SetAuthority {
+                current_authority: event.to_account_info(),
+                account_or_mint: ctx.accounts.ticket_mint.to_account_info(),
+            },
+            signer,
+        ),
+        AuthorityType::MintTokens,
+        None,
+    )?;
+
+    // Record ticket metadata
+    let ticket_data = &mut ctx.accounts.ticket_data;
+    ticket_data.event = event.key();
+    ticket_data.mint = ctx.accounts.ticket_mint.key();
+    ticket_data.tier = tier_index;
+    ticket_data.section = section;
+    ticket_data.row = row;
+    ticket_data.seat = seat;
+    ticket_data.face_value = face_value;
+    ticket_data.payment_mint = event.payment_mint;
+    ticket_data.minted_at = clock.unix_timestamp;
+    ticket_data.bump = ctx.bumps.ticket_data;
+
+    emit!(TicketMinted {
+        event: ticket_data.event,
+        mint: ticket_data.mint,
+        owner: ctx.accounts.recipient.key(),
+        tier: tier_index,
+        face_value,
+    });
+
+    msg!(
+        "Ticket minted: section {} row {} seat {} for {}",
+        ticket_data.section,
+        ticket_data.row,
+        ticket_data.seat,
+        ctx.accounts.recipient.key()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,10 @@
+// Every module exposes its own `handler`; lib.rs always calls them by full path.
+#![allow(ambiguous_glob_reexports)]
+
+pub mod create_event;
+pub mod mint_ticket;
+pub mod cancel_event;
+
+pub use create_event::*;
+pub use mint_ticket::*;
+pub use cancel_event::*;
@@ -1,16 +1,43 @@
 use anchor_lang::prelude::*;
 
+pub mod instructions;
+pub mod state;
+pub mod errors;
+
+use instructions::*;
+use state::*;
+
 declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
 
 #[program]
 pub mod ticket_nft_core {
     use super::*;
 
-    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
-        msg!("ticket-nft-core initialized!");
-        Ok(())
+    // Event management
+    pub fn create_event(
+        ctx: Context<CreateEvent>,
+        event_id: u64,
+        name: String,
+        venue: String,
+        start_time: i64,
+        end_time: i64,
+        tiers: Vec<TicketTier>,
+    ) -> Result<()> {
+        instructions::create_event::handler(ctx, event_id, name, venue, start_time, end_time, tiers)
     }
-}
 
-#[derive(Accounts)]
-pub struct Initialize {}
+    pub fn cancel_event(ctx: Context<CancelEvent>) -> Result<()> {
+        instructions::cancel_event::handler(ctx)
+    }
+
+    // Ticket minting
+    pub fn mint_ticket(
+        ctx: Context<MintTicket>,
+        tier_index: u8,
+        section: String,
+        row: String,
+        seat: String,
+    ) -> Result<()> {
+        instructions::mint_ticket::handler(ctx, tier_index, section, row, seat)
+    }
+}
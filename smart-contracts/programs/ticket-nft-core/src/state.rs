@@ -0,0 +1,131 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+#[account]
+pub struct Event {
+    pub authority: Pubkey,        // Organizer allowed to mint and manage the event
+    pub event_id: u64,            // Organizer-chosen id, part of the PDA seeds
+    pub name: String,
+    pub venue: String,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub tiers: Vec<TicketTier>,   // Pricing tiers, indexed by TicketData::tier
+    pub payment_mint: Option<Pubkey>, // SPL mint face values are quoted in (None = lamports)
+    pub total_supply: u32,        // Sum of all tier supplies
+    pub tickets_minted: u32,
+    pub cancelled: bool,          // Set by cancel_event; marketplace stops trading, refunds open
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl Event {
+    pub const MAX_NAME_LEN: usize = 64;
+    pub const MAX_VENUE_LEN: usize = 64;
+    pub const MAX_TIERS: usize = 10;
+
+    pub const MAX_SIZE: usize = 8 + // discriminator
+        32 + // authority
+        8 + // event_id
+        4 + Self::MAX_NAME_LEN + // name
+        4 + Self::MAX_VENUE_LEN + // venue
+        8 + // start_time
+        8 + // end_time
+        4 + (TicketTier::SIZE * Self::MAX_TIERS) + // tiers
+        1 + 32 + // payment_mint
+        4 + // total_supply
+        4 + // tickets_minted
+        1 + // cancelled
+        8 + // created_at
+        1; // bump
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct TicketTier {
+    pub name: String,             // "GA", "VIP", "Floor"
+    pub face_value: u64,          // Primary price in the event's payment currency
+    pub supply: u32,
+    pub minted: u32,
+}
+
+impl TicketTier {
+    pub const MAX_NAME_LEN: usize = 32;
+
+    pub const SIZE: usize = 4 + Self::MAX_NAME_LEN + // name
+        8 + // face_value
+        4 + // supply
+        4; // minted
+}
+
+/// Canonical on-chain record for a ticket mint, seeded by `[b"ticket", mint]`.
+/// Other programs check a mint against this account before treating it as a ticket.
+#[account]
+pub struct TicketData {
+    pub event: Pubkey,            // Event account this ticket belongs to
+    pub mint: Pubkey,             // Supply-1 SPL mint
+    pub tier: u8,                 // Index into Event::tiers
+    pub section: String,
+    pub row: String,
+    pub seat: String,
+    pub face_value: u64,          // Original price, used for resale caps
+    pub payment_mint: Option<Pubkey>, // Currency of face_value, copied from the event
+    pub minted_at: i64,
+    pub bump: u8,
+}
+
+impl TicketData {
+    pub const MAX_SEAT_FIELD_LEN: usize = 32;
+
+    /// Seed identifying a seat within an event. Each field is length-prefixed before hashing so
+    /// ("1", "12", "A") and ("11", "2", "A") stay distinct seats.
+    pub fn seat_seed(section: &str, row: &str, seat: &str) -> [u8; 32] {
+        let section_len = [section.len() as u8];
+        let row_len = [row.len() as u8];
+        let seat_len = [seat.len() as u8];
+        hashv(&[
+            &section_len,
+            section.as_bytes(),
+            &row_len,
+            row.as_bytes(),
+            &seat_len,
+            seat.as_bytes(),
+        ])
+        .to_bytes()
+    }
+
+    pub const MAX_SIZE: usize = 8 + // discriminator
+        32 + // event
+        32 + // mint
+        1 + // tier
+        4 + Self::MAX_SEAT_FIELD_LEN + // section
+        4 + Self::MAX_SEAT_FIELD_LEN + // row
+        4 + Self::MAX_SEAT_FIELD_LEN + // seat
+        8 + // face_value
+        1 + 32 + // payment_mint
+        8 + // minted_at
+        1; // bump
+}
+
+#[event]
+pub struct EventCreated {
+    pub event: Pubkey,
+    pub authority: Pubkey,
+    pub event_id: u64,
+    pub start_time: i64,
+    pub total_supply: u32,
+}
+
+#[event]
+pub struct EventCancelled {
+    pub event: Pubkey,
+    pub authority: Pubkey,
+    pub tickets_minted: u32,
+}
+
+#[event]
+pub struct TicketMinted {
+    pub event: Pubkey,
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub tier: u8,
+    pub face_value: u64,
+}
@@ -0,0 +1,21 @@
+use ticket_nft_core::state::TicketData;
+
+#[test]
+fn seat_seed_keeps_field_boundaries() {
+    assert_ne!(
+        TicketData::seat_seed("1", "12", "A"),
+        TicketData::seat_seed("11", "2", "A")
+    );
+    assert_ne!(
+        TicketData::seat_seed("A", "", "1"),
+        TicketData::seat_seed("", "A", "1")
+    );
+}
+
+#[test]
+fn seat_seed_is_stable_for_the_same_seat() {
+    assert_eq!(
+        TicketData::seat_seed("101", "F", "12"),
+        TicketData::seat_seed("101", "F", "12")
+    );
+}